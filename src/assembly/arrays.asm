@@ -0,0 +1,56 @@
+; ======================================
+; arrays.asm
+; Runtime routines for arrays. An array is a pointer to its length, which is followed by the elements:
+;
+;   [rax]               length
+;   [rax + 8 + i * 8]   element i
+;
+; The elements of a string array are strings of their own, which belong to the array. An array variable
+; holds 0 until its declaration has run for the first time. Like the routines in runtime.asm, every routine
+; preserves all registers except RAX.
+; ======================================
+
+; ======================================
+; medusa_free_array
+; Free the array in RCX, unless it's 0. This routine doesn't return anything, so it preserves every register,
+; including RAX.
+; ======================================
+medusa_free_array:
+cmp rcx, 0
+je medusa_free_array_return
+call medusa_free
+
+medusa_free_array_return:
+ret
+
+; ======================================
+; medusa_free_string_array
+; Free the string array in RCX along with every one of its strings, unless it's 0. This routine doesn't
+; return anything, so it preserves every register, including RAX.
+; ======================================
+medusa_free_string_array:
+cmp rcx, 0
+je medusa_free_string_array_return
+
+push rdx
+push r10
+
+; Use R10 to hold the index of the element being freed
+xor r10, r10
+
+medusa_free_string_array_loop:
+cmp r10, [rcx]
+jge medusa_free_string_array_break
+
+mov rdx, [rcx + r10 * 8 + 8]
+call medusa_free_string
+inc r10
+jmp medusa_free_string_array_loop
+
+medusa_free_string_array_break:
+pop r10
+pop rdx
+call medusa_free
+
+medusa_free_string_array_return:
+ret
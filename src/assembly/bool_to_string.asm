@@ -0,0 +1,37 @@
+; ======================================
+; bool_to_string.asm
+; Convert the bool (represented internally as 0 for false and 1 for true) stored on the stack into a string
+; (see strings.asm) and return the string using the stack
+; ======================================
+
+; Allocate a string for bool-to-string, which is big enough for "false"
+mov rcx, 5
+call medusa_string_new
+
+; Pick which word to copy, based on the bool
+pop rcx
+lea r8, [rel false_string]
+cmp rcx, 0
+je label_{copy_index}
+lea r8, [rel true_string]
+
+label_{copy_index}:
+xor rcx, rcx
+
+; Copy the word, including its null terminator
+label_{loop_index}:
+mov dl, [r8 + rcx]
+mov [rax + rcx], dl
+inc rcx
+cmp dl, 0
+jne label_{loop_index}
+
+; The null terminator isn't part of the string's length
+dec rcx
+mov [rax - 8], rcx
+
+push rax
+
+; ======================================
+; End of bool_to_string.asm
+; ======================================
@@ -0,0 +1,67 @@
+; ======================================
+; compare_strings.asm
+; Runtime routines for comparing strings. Unlike the other snippets, these are emitted once per program and
+; called, so they use fixed labels rather than {label} placeholders. Like the routines in runtime.asm, every
+; routine preserves all registers except RAX.
+; ======================================
+
+; ======================================
+; medusa_compare_strings
+; Compare the strings in RDX (left) and R8 (right) byte by byte, and return the result using RAX: -1 if left
+; comes first, 0 if the strings are equal, and 1 if right comes first. The bytes are compared as unsigned
+; numbers, and a string that runs out first (i.e. is a prefix of the other) comes first. The comparison goes
+; by the strings' lengths rather than their null terminators, since a string can contain null characters.
+; ======================================
+medusa_compare_strings:
+push rcx
+push r9
+push r10
+
+; Use R10 to hold the length of the shorter string
+mov r10, [rdx - 8]
+cmp r10, [r8 - 8]
+jbe medusa_compare_strings_begin
+mov r10, [r8 - 8]
+
+medusa_compare_strings_begin:
+; Use RCX to hold the index of the character being compared
+xor rcx, rcx
+
+medusa_compare_strings_loop:
+cmp rcx, r10
+je medusa_compare_strings_lengths
+
+movzx rax, byte [rdx + rcx]
+movzx r9, byte [r8 + rcx]
+
+cmp rax, r9
+jne medusa_compare_strings_different
+
+inc rcx
+jmp medusa_compare_strings_loop
+
+medusa_compare_strings_lengths:
+; Every character of the shorter string matched, so the shorter string comes first (or they're equal)
+mov rax, [rdx - 8]
+cmp rax, [r8 - 8]
+je medusa_compare_strings_equal
+
+medusa_compare_strings_different:
+; The flags are still set from the last comparison (mov and je don't change them)
+mov rax, -1
+jb medusa_compare_strings_return
+mov rax, 1
+jmp medusa_compare_strings_return
+
+medusa_compare_strings_equal:
+xor rax, rax
+
+medusa_compare_strings_return:
+pop r10
+pop r9
+pop rcx
+ret
+
+; ======================================
+; End of compare_strings.asm
+; ======================================
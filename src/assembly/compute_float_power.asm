@@ -1,10 +1,201 @@
 ; ======================================
 ; compute_float_power.asm
 ; Pop floats x and y (represented internally as doubles) off the stack, compute the result of x^y,
-; and return the result using RAX
+; and push the result back onto the stack
+;
+; The edge cases follow C's pow() (IEEE 754):
+;   x^0 and 1^y are always 1, even if the other operand is NaN
+;   Any other power involving NaN is NaN
+;   A negative x with a fractional y is NaN
+;   0^y is 0 for positive y, and infinity for negative y (keeping the sign of x when y is an odd integer)
 ; ======================================
 
+; Pop the left float (x) into RAX, and pop the right float (y) into RBX
+pop rbx
+pop rax
 
+movq xmm1, rax
+movq xmm2, rbx
+
+; Keep 1.0 in R8, we'll need it a lot
+mov r8, 0x3FF0000000000000
+
+; ======================================
+; x^0 = 1 and 1^y = 1
+; ======================================
+
+; ucomisd sets ZF for NaN as well, so PF has to be checked before trusting ZF
+xorpd xmm0, xmm0
+ucomisd xmm2, xmm0
+jp label_{y_is_not_zero}
+je label_{return_one}
+
+label_{y_is_not_zero}:
+movq xmm0, r8
+ucomisd xmm1, xmm0
+jp label_{x_is_not_one}
+je label_{return_one}
+
+label_{x_is_not_one}:
+
+; Anything else involving NaN is NaN
+ucomisd xmm1, xmm2
+jp label_{return_nan}
+
+; ======================================
+; Is y an integer? If it is, use exponentiation by squaring (just like compute_integer_power), which gives
+; exact results for things like 3.0 ^ 2.0
+; ======================================
+
+; cvttsd2si returns 0x8000000000000000 if y doesn't fit in 64 bits (including infinity)
+cvttsd2si rcx, xmm2
+mov rdx, 0x8000000000000000
+cmp rcx, rdx
+je label_{logarithm}
+
+; If converting y back to a float doesn't give the same number, then y has a fraction
+cvtsi2sd xmm0, rcx
+ucomisd xmm0, xmm2
+jne label_{fractional_power}
+
+; Remember the sign of y in RDX, and use the absolute value for the loop
+mov rdx, rcx
+cmp rcx, 0
+jge label_{square}
+neg rcx
+
+label_{square}:
+
+; Store the result in XMM0
+movq xmm0, r8
+
+label_{square_loop}:
+
+; Is y odd?
+test rcx, 1
+jz label_{y_is_even}
+
+mulsd xmm0, xmm1
+
+label_{y_is_even}:
+
+; Divide y by two. If it's zero, then we're done.
+shr rcx, 1
+jz label_{square_loop_break}
+
+mulsd xmm1, xmm1
+jmp label_{square_loop}
+
+label_{square_loop_break}:
+
+; x^-y = 1 / x^y
+cmp rdx, 0
+jge label_{return_result}
+
+movq xmm1, r8
+divsd xmm1, xmm0
+movapd xmm0, xmm1
+jmp label_{return_result}
+
+; ======================================
+; y has a fraction, so the result is only defined for positive x
+; ======================================
+
+label_{fractional_power}:
+xorpd xmm0, xmm0
+ucomisd xmm1, xmm0
+jb label_{return_nan}
+
+; ======================================
+; Everything else is calculated as 2^(y * log2(|x|)). y is either fractional, so big that it's guaranteed to
+; be an even integer, or infinite, so the sign of x doesn't matter from here on.
+; ======================================
+
+label_{logarithm}:
+btr rax, 63
+movq xmm1, rax
+
+; (-1)^infinity = 1
+movq xmm0, r8
+ucomisd xmm1, xmm0
+je label_{return_one}
+
+; When |x| is 0 or infinity, or y is infinite, y * log2(|x|) is infinite, and the x87 instructions below
+; can't deal with that. The result is either infinity or 0 depending on which way the exponent points.
+mov rcx, 0x7FF0000000000000
+cmp rax, 0
+je label_{infinite_exponent}
+cmp rax, rcx
+je label_{infinite_exponent}
+mov rdx, rbx
+btr rdx, 63
+cmp rdx, rcx
+je label_{infinite_exponent}
+
+; The x87 FPU can only load values from memory, so make some room on the stack
+sub rsp, 16
+movsd qword [rsp], xmm2
+movsd qword [rsp + 8], xmm1
+
+fld qword [rsp]         ; ST0 = y
+fld qword [rsp + 8]     ; ST0 = |x|, ST1 = y
+fyl2x                   ; ST0 = t = y * log2(|x|)
+
+; 2^t = 2^(t - round(t)) * 2^round(t), since f2xm1 only works on numbers between -1 and 1
+fld st0                 ; ST0 = t, ST1 = t
+frndint                 ; ST0 = n = round(t), ST1 = t
+fxch st1                ; ST0 = t, ST1 = n
+fsub st0, st1           ; ST0 = f = t - n, ST1 = n
+f2xm1                   ; ST0 = 2^f - 1, ST1 = n
+fld1
+faddp st1, st0          ; ST0 = 2^f, ST1 = n
+fscale                  ; ST0 = 2^f * 2^n, ST1 = n
+fstp st1                ; ST0 = 2^t
+
+fstp qword [rsp]
+movsd xmm0, qword [rsp]
+add rsp, 16
+jmp label_{return_result}
+
+label_{infinite_exponent}:
+; The exponent is positive (and the result is infinity) when |x| > 1 and y > 0, or when |x| < 1 and y < 0
+movq xmm0, r8
+ucomisd xmm1, xmm0
+seta cl
+
+xorpd xmm0, xmm0
+ucomisd xmm2, xmm0
+seta dl
+
+cmp cl, dl
+je label_{return_infinity}
+
+; 0
+xorpd xmm0, xmm0
+jmp label_{return_result}
+
+label_{return_infinity}:
+mov rcx, 0x7FF0000000000000
+movq xmm0, rcx
+jmp label_{return_result}
+
+label_{return_nan}:
+mov rcx, 0x7FF8000000000000
+movq xmm0, rcx
+jmp label_{return_result}
+
+label_{return_one}:
+movq xmm0, r8
+
+; ======================================
+; Return the result
+; ======================================
+
+label_{return_result}:
+
+; Push the result
+movq rax, xmm0
+push rax
 
 ; ======================================
 ; End of compute_float_power.asm
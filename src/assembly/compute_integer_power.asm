@@ -1,13 +1,15 @@
 ; ======================================
 ; compute_integer_power.asm
 ; Pop integers x and y (represented internally as 64-bit signed integers) off the stack, compute the result
-; of x^y, and return the result using RAX
+; of x^y, and push the result. R9 is set to 1 if the result overflowed at any point, and 0 otherwise.
 ; ======================================
 
 ; Pop the left integer (x) into rax, and pop the right integer (y) into rbx
 pop rbx
 pop rax
 
+xor r9, r9
+
 ; If y is negative, don't bother calculating the power
 mov rcx, 0
 cmp rbx, 0
@@ -31,6 +33,8 @@ cmp rdx, 1
 jne label_{y_is_even}
 
 imul rcx, rax
+seto r10b
+or r9b, r10b
 
 label_{y_is_even}:
 
@@ -39,6 +43,8 @@ shr rbx, 1
 jz label_{square_loop_break}
 
 imul rax, rax
+seto r10b
+or r9b, r10b
 jmp label_{square_loop}
 
 label_{square_loop_break}:
@@ -1,24 +1,32 @@
 ; ======================================
 ; float_to_string.asm
-; Convert the float (represented internally as a double) stored in RAX into a string (represented internally as a null-terminated string)
+; Convert the float (represented internally as a double) stored in RAX into a string (see strings.asm)
 ; and return the string using RAX
 ; ======================================
 
-; Pop the float off the stack and move it into xmm1
-pop rax
-movq xmm1, rax
-
-; Allocate space for float-to-string on the heap
-mov rcx, [rel heap_handle]
-mov rdx, 12
-mov r8, 256
-sub rsp, 32
-call HeapAlloc
-add rsp, 32
+; Allocate a string for float-to-string - the quotient is at most 19 digits long, so there's plenty of room for
+; it along with the minus sign, the dot, and the 6 digits after it
+mov rcx, 32
+call medusa_string_new
 
 ; The pointer to the string is stored in r8
 mov r8, rax
 
+; Pop the float off the stack
+pop rax
+
+; If the float is negative, set r15 to 1, then clear the sign bit so that the number is positive for the rest of
+; the calculations
+xor r15, r15
+btr rax, 63
+jnc label_{not_negative}
+mov r15, 1
+
+label_{not_negative}:
+
+; Move the float into xmm1
+movq xmm1, rax
+
 ; Store the quotient in rbx
 cvttsd2si rbx, xmm1
 
@@ -33,7 +41,7 @@ subsd xmm1, xmm2
 mov r14, 1000000
 cvtsi2sd xmm2, r14
 mulsd xmm1, xmm2
-cvtsd2si rcx, xmm1
+cvttsd2si rcx, xmm1
 
 ; The index into the string is stored in r9
 xor r9, r9
@@ -107,7 +115,7 @@ jne label_{skip_negation}
 
 ; Add the minus sign to the end - it will be at the beginning once we reverse the string
 mov byte [r8 + r9], 45 ; ASCII number for '-'
-inc rcx
+inc r9
 
 label_{skip_negation}:
 
@@ -134,7 +142,8 @@ dec r11
 cmp rax, r10
 jnz label_{loop_index_3}
 
-; Push the final string onto the stack
+; Store the string's length, then push the final string onto the stack
+mov [r8 - 8], r9
 push r8
 
 ; ======================================
@@ -1,16 +1,12 @@
 ; ======================================
 ; int_to_string.asm
-; Convert the int (represented internally as a 64-bit signed integer) stored in RAX into a string (represented internally as a null-terminated string)
+; Convert the int (represented internally as a 64-bit signed integer) stored in RAX into a string (see strings.asm)
 ; and return the string using RAX
 ; ======================================
 
-; Allocate space for int-to-string on the heap
-mov rcx, [rel heap_handle]
-mov rdx, 12
-mov r8, 256
-sub rsp, 32
-call HeapAlloc
-add rsp, 32
+; Allocate a string for int-to-string - the longest int is 20 characters long, including the minus sign
+mov rcx, 20
+call medusa_string_new
 
 ; Store empty string in R8
 mov r8, rax
@@ -88,6 +84,8 @@ jnz label_{loop_index_2}
 
 label_{break_index}:
 
+; Store the string's length
+mov [r8 - 8], rcx
 
 push r8
 
@@ -0,0 +1,229 @@
+; ======================================
+; linux/runtime.asm
+; Runtime routines for Linux x86-64, built directly on top of system calls. Every routine preserves all
+; registers except RAX, which holds the return value (if there is one).
+; ======================================
+
+; ======================================
+; medusa_alloc
+; Allocate RCX bytes of zeroed memory and return a pointer to it using RAX. Every allocation is rounded up
+; (along with a 16-byte header) to a power of two, called its size class. Memory that has been freed is kept
+; in a list for its size class and handed out again, and everything else comes from chunks that are mapped
+; with mmap. Nothing is ever given back to the operating system.
+;
+;   [rax - 16]  size class
+;   [rax - 8]   next block in the free list, while the block is free
+; ======================================
+medusa_alloc:
+push rcx
+push rdx
+push rsi
+push rdi
+push r8
+push r9
+push r10
+push r11
+
+; Work out the size class, i.e. the smallest power of two that fits the allocation and its header
+add rcx, 15
+bsr rsi, rcx
+inc rsi
+
+; Use RCX to hold the size of the block
+mov rcx, rsi
+mov rdx, 1
+shl rdx, cl
+mov rcx, rdx
+
+; Is there a freed block of the same size class that can be handed out again?
+lea rdi, [rel free_lists]
+mov rax, [rdi + rsi * 8]
+cmp rax, 0
+je medusa_alloc_new_block
+
+mov rdx, [rax + 8]
+mov [rdi + rsi * 8], rdx
+
+; The block has been used before, so it has to be zeroed (apart from the header)
+push rax
+lea rdi, [rax + 16]
+sub rcx, 16
+xor rax, rax
+rep stosb
+pop rax
+jmp medusa_alloc_return
+
+medusa_alloc_new_block:
+; Is there enough room left in the current chunk?
+mov rax, [rel heap_pointer]
+mov rdx, rax
+add rdx, rcx
+cmp rdx, [rel heap_end]
+jbe medusa_alloc_from_chunk
+
+; There isn't, so map a new chunk that is at least big enough to hold the allocation
+push rsi
+mov rsi, heap_chunk_size
+cmp rcx, rsi
+jbe medusa_alloc_map_chunk
+mov rsi, rcx
+
+medusa_alloc_map_chunk:
+push rcx
+mov rax, 9 ; sys_mmap
+xor rdi, rdi
+mov rdx, 3 ; PROT_READ | PROT_WRITE
+mov r10, 0x22 ; MAP_PRIVATE | MAP_ANONYMOUS
+mov r8, -1
+xor r9, r9
+syscall
+pop rcx
+
+; The end of the chunk is remembered so that we know when to map the next one
+mov rdx, rax
+add rdx, rsi
+mov [rel heap_end], rdx
+pop rsi
+
+mov rdx, rax
+add rdx, rcx
+
+medusa_alloc_from_chunk:
+mov [rel heap_pointer], rdx
+
+; Chunks are zeroed by mmap, so all that's left is to fill in the header
+mov [rax], rsi
+
+medusa_alloc_return:
+add rax, 16
+
+pop r11
+pop r10
+pop r9
+pop r8
+pop rdi
+pop rsi
+pop rdx
+pop rcx
+ret
+
+; ======================================
+; medusa_free
+; Free the memory in RCX, which must have come from medusa_alloc. This routine doesn't return anything, so
+; it preserves every register, including RAX.
+; ======================================
+medusa_free:
+push rax
+push rdx
+push rsi
+
+; Push the block onto the front of the free list for its size class
+lea rax, [rcx - 16]
+mov rsi, [rax]
+lea rdx, [rel free_lists]
+push qword [rdx + rsi * 8]
+pop qword [rax + 8]
+mov [rdx + rsi * 8], rax
+
+pop rsi
+pop rdx
+pop rax
+ret
+
+; ======================================
+; medusa_write
+; Write R8 bytes from the buffer in RDX to standard output
+; ======================================
+medusa_write:
+push rcx
+push rdx
+push rsi
+push rdi
+push r11
+
+mov rax, 1 ; sys_write
+mov rdi, 1 ; stdout
+mov rsi, rdx
+mov rdx, r8
+syscall
+
+pop r11
+pop rdi
+pop rsi
+pop rdx
+pop rcx
+ret
+
+; ======================================
+; medusa_read
+; Read a single line (up to R8 bytes, including the newline) from standard input into the buffer in RDX,
+; and return the number of bytes read using RAX. The input is read one byte at a time so that nothing
+; past the end of the line is consumed.
+; ======================================
+medusa_read:
+push rcx
+push rdx
+push rsi
+push rdi
+push r9
+push r11
+
+; RSI points to the next free byte in the buffer, and R9 counts the bytes read so far
+mov rsi, rdx
+xor r9, r9
+
+medusa_read_loop:
+cmp r9, r8
+jae medusa_read_break
+
+xor rax, rax ; sys_read
+xor rdi, rdi ; stdin
+mov rdx, 1
+syscall
+
+; Stop at the end of the input (or if something went wrong)
+cmp rax, 1
+jne medusa_read_break
+
+inc r9
+mov al, [rsi]
+inc rsi
+
+; Stop once the newline has been read
+cmp al, 10
+jne medusa_read_loop
+
+medusa_read_break:
+mov rax, r9
+
+pop r11
+pop r9
+pop rdi
+pop rsi
+pop rdx
+pop rcx
+ret
+
+; ======================================
+; medusa_exit
+; Terminate the process with the exit code stored in RCX
+; ======================================
+medusa_exit:
+mov rdi, rcx
+mov rax, 60 ; sys_exit
+syscall
+
+section .data
+heap_pointer dq 0
+heap_end dq 0
+heap_chunk_size equ 1048576
+
+section .bss
+; The first freed block of every size class, indexed by the size class
+free_lists resq 64
+
+section .text
+
+; ======================================
+; End of linux/runtime.asm
+; ======================================
@@ -0,0 +1,14 @@
+; ======================================
+; linux/startup.asm
+; Entry point for Linux x86-64 executables. The kernel jumps straight to _start with a 16-byte aligned stack,
+; so there are no handles or heaps to set up before the program body runs.
+; ======================================
+
+global _start
+
+section .text
+_start:
+
+; ======================================
+; End of linux/startup.asm
+; ======================================
@@ -1,7 +1,7 @@
 ; ======================================
 ; string_to_float.asm
 ; Convert the string (represented internally as a null-terminated string) stored in RAX into a float (represented internally as a double)
-; and return the float using RAX
+; and return the float using RAX. RCX is set to 1 if the string was a valid float, or 0 if it wasn't.
 ; ======================================
 
 ; Store the string in RAX
@@ -24,6 +24,9 @@ xor r8, r8
 ; Store the negative flag in r14 (0 if the float is positive, 1 if the float is negative)
 xor r14, r14
 
+; The number of digits read, before and after the decimal point - a valid float needs at least one
+xor r11, r11
+
 ; ======================================
 ; Read the quotient
 ; ======================================
@@ -82,6 +85,7 @@ jg label_{bad_input}
 imul rbx, 10
 add rbx, r8
 sub rbx, 48 ; ASCII code for '0'
+inc r11
 
 jmp label_{quotient_loop_index}
 
@@ -123,6 +127,7 @@ jg label_{bad_input}
 imul rdx, 10
 add rdx, r8
 sub rdx, 48 ; ASCII code for '0'
+inc r11
 
 jmp label_{fraction_loop_index}
 
@@ -134,6 +139,10 @@ jmp label_{fraction_loop_index}
 
 label_{start_processing_quotient_and_fraction}:
 
+; A sign or a decimal point on its own isn't a float
+cmp r11, 0
+je label_{bad_input}
+
 ; Move rdx into our float and divide it so that it's past the decimal point
 cvtsi2sd xmm1, rdx
 
@@ -153,6 +162,9 @@ jl label_{division_loop_index}
 cvtsi2sd xmm2, rbx
 addpd xmm1, xmm2
 
+; The string is a valid float
+mov rcx, 1
+
 ; ... Well, almost! Check and see if the float is supposed to be negative
 cmp r14, 1
 jne label_{end}
@@ -175,6 +187,7 @@ label_{bad_input}:
 ; The string is not a valid float, so we will return 0.0
 xor rbx, rbx
 cvtsi2sd xmm1, rbx
+xor rcx, rcx
 
 label_{end}:
 ; Regardless if the string was a valid float or not,
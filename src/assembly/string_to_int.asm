@@ -1,7 +1,7 @@
 ; ======================================
 ; string_to_int.asm
 ; Convert the string (represented internally as a null-terminated string) stored in RAX into an int (represented internally as a 64-bit signed integer) 
-; and return the int using RAX
+; and return the int using RAX. RCX is set to 1 if the string was a valid int, or 0 if it wasn't.
 ; ======================================
 
 ; Pop the string into RAX
@@ -18,6 +18,9 @@ xor rdx, rdx
 ; Flag set to 1 if the number is negative, 0 if positive
 xor r15, r15
 
+; The number of digits read - a valid int needs at least one
+xor r9, r9
+
 ; if (str[0] == '+') {{ i++; }}
 mov dl, [rax]
 cmp rdx, 43
@@ -48,6 +51,15 @@ mov dl, [rax + rcx]
 cmp rdx, 32
 jl label_{break_index}
 
+; If the string is not a valid int, then we need to cancel the conversion process
+; if (str[i] < '0' || str[i] > '9') {{
+;   goto bad_input;
+; }}
+cmp rdx, '0'
+jl label_{bad_input}
+cmp rdx, '9'
+jg label_{bad_input}
+
 ; Multiply the result by 10 by adding together result * 2 + result * 8
 mov r8, rbx
 sal r8, 1
@@ -58,17 +70,33 @@ add rbx, r8
 sub rdx, 48
 add rbx, rdx
 
+inc r9
 inc rcx
 jmp label_{loop_index}
 
 label_{break_index}:
 
+; A sign on its own (or nothing at all) isn't an int
+cmp r9, 0
+je label_{bad_input}
+
+; The string is a valid int
+mov rcx, 1
+
 ; Lastly, check if the number was supposed to be negative - if it was, negate it
 cmp r15, 1
 jne label_{finished_index}
 
 neg rbx
 
+; We did not run into any issues, so we'll jump over the bad_input label
+jmp label_{finished_index}
+
+label_{bad_input}:
+; The string is not a valid int, so we will return 0
+xor rbx, rbx
+xor rcx, rcx
+
 label_{finished_index}:
 ; Push the resulting int to the stack
 push rbx
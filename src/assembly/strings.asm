@@ -0,0 +1,239 @@
+; ======================================
+; strings.asm
+; Runtime routines for strings. A string is a pointer to its characters, which are always followed by a null
+; terminator. The 16 bytes in front of the characters hold the string's capacity (how many characters fit
+; before it has to grow) and its length:
+;
+;   [rax - 16]  capacity
+;   [rax - 8]   length
+;   [rax]       characters, followed by a null terminator
+;
+; Strings are never changed once they've been built, except for the strings that belong to variables, which
+; are only ever changed by medusa_assign_string. Every string variable owns its string, and frees it when it
+; has to be replaced with a bigger one. The generated code frees temporary strings (e.g. the result of a
+; concatenation) as soon as it's done with them. Like the routines in runtime.asm, every routine preserves
+; all registers except RAX.
+; ======================================
+
+; ======================================
+; medusa_string_new
+; Allocate an empty string with room for RCX characters and return it using RAX
+; ======================================
+medusa_string_new:
+push rcx
+
+; Make room for the capacity, the length, and the null terminator. The memory is zeroed, so the length
+; starts off as 0 and the string is already terminated.
+add rcx, 17
+call medusa_alloc
+
+pop rcx
+mov [rax], rcx
+add rax, 16
+ret
+
+; ======================================
+; medusa_free_string
+; Free the string in RDX, which must have come from medusa_string_new. Like medusa_free, this routine
+; preserves every register, including RAX.
+; ======================================
+medusa_free_string:
+push rcx
+
+lea rcx, [rdx - 16]
+call medusa_free
+
+pop rcx
+ret
+
+; ======================================
+; medusa_copy_string
+; Build a new string with the same characters as the string in RDX, and return it using RAX
+; ======================================
+medusa_copy_string:
+push rcx
+push rsi
+push rdi
+
+mov rcx, [rdx - 8]
+call medusa_string_new
+mov [rax - 8], rcx
+
+mov rsi, rdx
+mov rdi, rax
+rep movsb
+
+pop rdi
+pop rsi
+pop rcx
+ret
+
+; ======================================
+; medusa_assign_string
+; Copy the string in R8 into the string variable whose address is in RDX, growing the variable's string if
+; the new value doesn't fit
+; ======================================
+medusa_assign_string:
+push rcx
+push rsi
+push rdi
+
+; Use RCX to hold the length of the new value
+mov rcx, [r8 - 8]
+
+mov rax, [rdx]
+cmp rcx, [rax - 16]
+jbe medusa_assign_string_copy
+
+; It doesn't fit, so replace the variable's string with one that's either big enough or twice as big as
+; before, whichever is larger - doubling keeps a loop that keeps adding to a string from copying it every time
+mov rax, [rax - 16]
+shl rax, 1
+cmp rax, rcx
+jae medusa_assign_string_grow
+mov rax, rcx
+
+medusa_assign_string_grow:
+push rcx
+mov rcx, rax
+call medusa_string_new
+pop rcx
+
+; The old string isn't needed anymore
+push rdx
+mov rdx, [rdx]
+call medusa_free_string
+pop rdx
+
+mov [rdx], rax
+
+medusa_assign_string_copy:
+mov [rax - 8], rcx
+
+; Copy the characters along with the null terminator. Assigning a variable to itself copies the string
+; onto itself, which leaves it unchanged.
+inc rcx
+mov rsi, r8
+mov rdi, rax
+rep movsb
+
+pop rdi
+pop rsi
+pop rcx
+ret
+
+; ======================================
+; medusa_concatenate_strings
+; Build a new string out of the string in RDX followed by the string in R8, and return it using RAX.
+; Neither of the two strings is changed.
+; ======================================
+medusa_concatenate_strings:
+push rcx
+push rsi
+push rdi
+
+mov rcx, [rdx - 8]
+add rcx, [r8 - 8]
+call medusa_string_new
+mov [rax - 8], rcx
+
+; Copy the left string, then the right string straight after it. The new string is already terminated.
+mov rdi, rax
+mov rsi, rdx
+mov rcx, [rdx - 8]
+rep movsb
+
+mov rsi, r8
+mov rcx, [r8 - 8]
+rep movsb
+
+pop rdi
+pop rsi
+pop rcx
+ret
+
+; ======================================
+; medusa_read_line
+; Read a line from standard input, no matter how long it is, and return it as a new string using RAX. The
+; line ends at the first carriage return or newline, which isn't included in the string.
+; ======================================
+medusa_read_line:
+push rcx
+push rdx
+push rsi
+push rdi
+push r8
+push r9
+
+mov rcx, 64
+call medusa_string_new
+
+medusa_read_line_loop:
+; Read into the space after everything that has been read so far
+mov r9, [rax - 8]
+lea rdx, [rax + r9]
+mov r8, [rax - 16]
+sub r8, r9
+
+push rax
+call medusa_read
+mov rcx, rax
+pop rax
+add [rax - 8], rcx
+
+; The line is over if fewer bytes were read than there was room for, or if the last byte is a newline
+cmp rcx, r8
+jb medusa_read_line_strip
+
+mov r9, [rax - 8]
+cmp byte [rax + r9 - 1], 10
+je medusa_read_line_strip
+
+; The line didn't fit, so move it into a string that's twice as big and keep reading
+mov rdx, rax
+mov rcx, [rdx - 16]
+shl rcx, 1
+call medusa_string_new
+
+mov rcx, [rdx - 8]
+mov [rax - 8], rcx
+mov rsi, rdx
+mov rdi, rax
+rep movsb
+
+call medusa_free_string
+jmp medusa_read_line_loop
+
+medusa_read_line_strip:
+; Cut the line off at the first carriage return or newline
+xor rcx, rcx
+
+medusa_read_line_strip_loop:
+cmp rcx, [rax - 8]
+jae medusa_read_line_return
+
+mov r9b, [rax + rcx]
+cmp r9b, 13 ; ASCII code for carriage return
+je medusa_read_line_cut
+cmp r9b, 10 ; ASCII code for newline character
+je medusa_read_line_cut
+
+inc rcx
+jmp medusa_read_line_strip_loop
+
+medusa_read_line_cut:
+mov [rax - 8], rcx
+mov byte [rax + rcx], 0
+
+medusa_read_line_return:
+pop r9
+pop r8
+pop rdi
+pop rsi
+pop rdx
+pop rcx
+ret
+
+; ======================================
+; End of strings.asm
+; ======================================
@@ -0,0 +1,191 @@
+; ======================================
+; windows/runtime.asm
+; Runtime routines for Win64, built on top of kernel32. Every routine preserves all registers except RAX,
+; which holds the return value (if there is one), and aligns the stack before calling into the WinApi.
+; ======================================
+
+; ======================================
+; medusa_alloc
+; Allocate RCX bytes of zeroed memory and return a pointer to it using RAX
+; ======================================
+medusa_alloc:
+push rbp
+mov rbp, rsp
+push rcx
+push rdx
+push r8
+push r9
+push r10
+push r11
+
+mov r8, rcx
+mov rcx, [rel heap_handle]
+mov rdx, 12 ; HEAP_ZERO_MEMORY | HEAP_GENERATE_EXCEPTIONS
+and rsp, -16
+sub rsp, 32
+call HeapAlloc
+
+lea rsp, [rbp - 48]
+pop r11
+pop r10
+pop r9
+pop r8
+pop rdx
+pop rcx
+pop rbp
+ret
+
+; ======================================
+; medusa_free
+; Free the memory in RCX, which must have come from medusa_alloc. This routine doesn't return anything, so
+; it preserves every register, including RAX.
+; ======================================
+medusa_free:
+push rbp
+mov rbp, rsp
+push rax
+push rcx
+push rdx
+push r8
+push r9
+push r10
+push r11
+
+mov r8, rcx
+mov rcx, [rel heap_handle]
+xor rdx, rdx
+and rsp, -16
+sub rsp, 32
+call HeapFree
+
+lea rsp, [rbp - 56]
+pop r11
+pop r10
+pop r9
+pop r8
+pop rdx
+pop rcx
+pop rax
+pop rbp
+ret
+
+; ======================================
+; medusa_write
+; Write R8 bytes from the buffer in RDX to standard output
+; ======================================
+medusa_write:
+push rbp
+mov rbp, rsp
+push rcx
+push rdx
+push r8
+push r9
+push r10
+push r11
+
+mov rcx, [rel output_handle]
+lea r9, [rel ignore]
+and rsp, -16
+sub rsp, 48
+mov qword [rsp + 32], 0
+call WriteFile
+
+lea rsp, [rbp - 48]
+pop r11
+pop r10
+pop r9
+pop r8
+pop rdx
+pop rcx
+pop rbp
+ret
+
+; ======================================
+; medusa_read
+; Read a single line (up to R8 bytes, including the newline) from standard input into the buffer in RDX,
+; and return the number of bytes read using RAX. ReadFile returns everything that's available on a pipe or
+; file, so the input is read one byte at a time so that nothing past the end of the line is consumed.
+; ======================================
+medusa_read:
+push rbp
+mov rbp, rsp
+push rcx
+push rdx
+push r8
+push r9
+push r10
+push r11
+push rbx
+push rsi
+push rdi
+
+; RSI points to the next free byte in the buffer, RDI counts the bytes read so far, and RBX holds the limit.
+; They're all preserved across calls.
+mov rsi, rdx
+xor rdi, rdi
+mov rbx, r8
+
+and rsp, -16
+sub rsp, 48
+
+medusa_read_loop:
+cmp rdi, rbx
+jae medusa_read_break
+
+mov rcx, [rel input_handle]
+mov rdx, rsi
+mov r8, 1
+lea r9, [rel bytes_read]
+mov qword [rsp + 32], 0
+call ReadFile
+
+; Stop at the end of the input (or if something went wrong)
+test eax, eax
+jz medusa_read_break
+cmp dword [rel bytes_read], 1
+jne medusa_read_break
+
+inc rdi
+mov al, [rsi]
+inc rsi
+
+; Stop once the newline has been read
+cmp al, 10
+jne medusa_read_loop
+
+medusa_read_break:
+mov rax, rdi
+
+lea rsp, [rbp - 72]
+pop rdi
+pop rsi
+pop rbx
+pop r11
+pop r10
+pop r9
+pop r8
+pop rdx
+pop rcx
+pop rbp
+ret
+
+; ======================================
+; medusa_exit
+; Terminate the process with the exit code stored in RCX
+; ======================================
+medusa_exit:
+and rsp, -16
+sub rsp, 32
+call ExitProcess
+
+section .data
+input_handle dq 0
+output_handle dq 0
+heap_handle dq 0
+bytes_read dq 0
+
+section .text
+
+; ======================================
+; End of windows/runtime.asm
+; ======================================
@@ -0,0 +1,36 @@
+; ======================================
+; windows/startup.asm
+; Entry point for Win64 executables. Grabs the standard input/output handles and the process heap so that
+; the runtime routines can use them later.
+; ======================================
+
+global mainCRTStartup
+
+extern MessageBoxA
+extern WriteFile
+extern ReadFile
+extern GetStdHandle
+extern ExitProcess
+extern GetProcessHeap
+extern HeapAlloc
+extern HeapFree
+
+section .text
+mainCRTStartup:
+
+sub rsp, 40
+
+mov rcx, -10
+call GetStdHandle
+mov [rel input_handle], rax
+
+mov rcx, -11
+call GetStdHandle
+mov [rel output_handle], rax
+
+call GetProcessHeap
+mov [rel heap_handle], rax
+
+; ======================================
+; End of windows/startup.asm
+; ======================================
@@ -0,0 +1,302 @@
+// The abstract syntax tree for Medusa programs. The tree is produced by the checker (see checker.rs), so by
+// the time anything else sees it, every variable has been declared and every expression has a resolved
+// datatype. Code generation and the interpreter both consume this tree rather than the raw pest pairs.
+//
+// Variable names in the tree are unique across the whole program - a variable declared inside of a block is
+// renamed by the checker, so that it can't be confused with another variable of the same name in a
+// different scope.
+
+pub use crate::VariableDataType;
+
+/// A range of bytes in the source text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Returns the span that covers both this span and the other one.
+    pub fn to(&self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+impl From<pest::Span<'_>> for Span {
+    fn from(span: pest::Span) -> Span {
+        Span {
+            start: span.start(),
+            end: span.end(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    pub statements: Vec<Statement>,
+    /// Every variable in the program (outside of functions) along with its datatype, sorted by name.
+    pub variables: Vec<(String, VariableDataType)>,
+    /// Every array in the program (outside of functions) along with the datatype of its elements, sorted
+    /// by name.
+    pub arrays: Vec<(String, VariableDataType)>,
+    pub functions: Vec<Function>,
+}
+
+/// `int add(int a, int b) { return a + b; }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub return_datatype: VariableDataType,
+    pub parameters: Vec<Parameter>,
+    pub body: Vec<Statement>,
+    /// Every variable in the function (including the parameters) along with its datatype, sorted by name.
+    /// Each call to the function gets its own copy of these.
+    pub variables: Vec<(String, VariableDataType)>,
+    /// Every array in the function along with the datatype of its elements, sorted by name.
+    pub arrays: Vec<(String, VariableDataType)>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub datatype: VariableDataType,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Declaration(Declaration),
+    ArrayDeclaration(ArrayDeclaration),
+    Assignment(Assignment),
+    ElementAssignment(ElementAssignment),
+    Output(Output),
+    Input(Input),
+    If(If),
+    ForLoop(ForLoop),
+    While(While),
+    Return(Return),
+    /// `break` - jumps out of the innermost loop.
+    Break(Span),
+    /// `continue` - skips the rest of the innermost loop's body. A for loop still runs its step.
+    Continue(Span),
+}
+
+/// `int x;`, `int x = 5;`, or `int x <- @;`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Declaration {
+    pub datatype: VariableDataType,
+    pub name: String,
+    pub value: DeclarationValue,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeclarationValue {
+    None,
+    Assignment(Assignment),
+    Input(Input),
+}
+
+/// `int[10] scores`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrayDeclaration {
+    pub element_datatype: VariableDataType,
+    pub name: String,
+    /// The number of elements, which is worked out when the declaration runs.
+    pub length: Expression,
+    pub span: Span,
+}
+
+/// `x = 5`. `x += 5` and `x++` are turned into assignments too, e.g. `x = x + 5`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub name: String,
+    pub value: Expression,
+    pub span: Span,
+}
+
+/// `scores[i] = 5`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementAssignment {
+    pub name: String,
+    pub index: Expression,
+    pub value: Expression,
+    pub span: Span,
+}
+
+/// `x -> @`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Output {
+    pub value: Expression,
+    pub span: Span,
+}
+
+/// `x <- @`, or `x <- @ else { ... }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input {
+    pub name: String,
+    pub datatype: VariableDataType,
+    /// Runs when the input isn't a valid number. Without it, invalid input is a runtime error.
+    pub else_body: Option<Vec<Statement>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct If {
+    pub condition: Expression,
+    pub body: Vec<Statement>,
+    /// Checked in order after the if condition, the first one that's true has its body run
+    pub else_ifs: Vec<ElseIf>,
+    pub else_body: Option<Vec<Statement>>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElseIf {
+    pub condition: Expression,
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForLoop {
+    /// The declaration or assignment that runs before the loop starts (if there is one).
+    pub initializer: Option<Box<Statement>>,
+    pub condition: Expression,
+    pub step: Assignment,
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct While {
+    pub condition: Expression,
+    pub body: Vec<Statement>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionalOperator {
+    Greater,
+    Less,
+    GreaterOrEqual,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl ConditionalOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConditionalOperator::Greater => ">",
+            ConditionalOperator::Less => "<",
+            ConditionalOperator::GreaterOrEqual => ">=",
+            ConditionalOperator::LessOrEqual => "<=",
+            ConditionalOperator::Equal => "==",
+            ConditionalOperator::NotEqual => "!=",
+        }
+    }
+}
+
+/// `return x`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Return {
+    pub value: Expression,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub datatype: VariableDataType,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionKind {
+    Int(i64),
+    Float(f64),
+    /// The bytes of a string literal, with its escape sequences already decoded. A string can hold any bytes
+    /// (\xNN can make ones that aren't valid UTF-8), so it isn't a Rust String.
+    String(Vec<u8>),
+    Bool(bool),
+    Variable(String),
+    Binary {
+        operator: BinaryOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    /// Compares two operands of the same datatype, producing a bool.
+    Comparison {
+        operator: ConditionalOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    /// `&&` and `||` - the right operand is only evaluated if the left one doesn't decide the result.
+    Logical {
+        operator: LogicalOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    Not(Box<Expression>),
+    /// Flips the sign of an int or float, e.g. `-x`.
+    Negate(Box<Expression>),
+    /// Reads an element of an array, with the index checked against the array's length.
+    Element {
+        name: String,
+        index: Box<Expression>,
+    },
+    /// The number of elements in an array, e.g. `#scores`.
+    Length(String),
+    /// Calls a function, with the arguments in the same order as the function's parameters.
+    Call {
+        name: String,
+        arguments: Vec<Expression>,
+    },
+    /// Converts the operand to the datatype of the cast expression.
+    Cast(Box<Expression>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    Concatenate,
+}
+
+impl BinaryOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Modulo => "%",
+            BinaryOperator::Power => "^",
+            BinaryOperator::Concatenate => "..",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicalOperator {
+    And,
+    Or,
+}
+
+impl LogicalOperator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogicalOperator::And => "&&",
+            LogicalOperator::Or => "||",
+        }
+    }
+}
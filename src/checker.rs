@@ -0,0 +1,1793 @@
+// The semantic analysis pass. The checker walks the pairs produced by MedusaParser, makes sure that every
+// variable is declared and every operation is applied to the right datatypes, and builds the typed AST that
+// the code generator and the interpreter run on.
+//
+// The checker doesn't stop at the first error. Errors are collected in the context, and every check_*
+// function returns None when the code it checked is invalid, so the rest of the file can still be checked.
+// An expression that contains an error is never type checked any further, so one mistake (such as an
+// undeclared variable) doesn't cause a pile of mismatched datatype errors.
+//
+// Variables belong to the block they're declared in. Every block has its own scope, so a variable declared
+// inside of an if statement or a loop can't be seen outside of it, and a variable in an inner scope shadows
+// any variable with the same name in the scopes around it. The checker gives every variable a name that's
+// unique across the whole program (see `CheckerContext::declare_variable`), which is the name used in the
+// AST, so the code generator and the interpreter never have to think about scopes.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use pest::error::InputLocation;
+use pest::iterators::Pair;
+use pest::pratt_parser::{Assoc, Op, PrattParser};
+use pest::Parser;
+
+use crate::ast::*;
+use crate::{CompileError, Diagnostic, ErrorKind, MedusaParser, Rule};
+
+struct CheckerContext {
+    /// The scopes around the code being checked, innermost last. Each function starts off with a scope
+    /// stack of its own, so this is swapped out while a function is being checked.
+    scopes: Vec<Scope>,
+    /// How many scopes have been opened so far, used to give every scope a different index.
+    scope_count: usize,
+    /// The unique name and datatype of every variable declared in the program (or in the function being
+    /// checked).
+    variables: HashMap<String, VariableDataType>,
+    /// The unique name and element datatype of every array declared in the program (or in the function
+    /// being checked).
+    arrays: HashMap<String, VariableDataType>,
+    /// Every function in the program. These are collected before anything else is checked, so functions
+    /// can be called before they're declared (and can call themselves).
+    functions: HashMap<String, FunctionSignature>,
+    /// The name and return datatype of the function being checked, if there is one.
+    current_function: Option<(String, VariableDataType)>,
+    /// How many loops the code being checked is inside of. A function's body starts off outside of any
+    /// loop, even if the function is called from one.
+    loop_depth: usize,
+    errors: Vec<Diagnostic>,
+}
+
+struct Scope {
+    index: usize,
+    /// Maps the name of every variable declared in this scope to the variable.
+    variables: HashMap<String, ScopedVariable>,
+}
+
+#[derive(Clone)]
+struct ScopedVariable {
+    unique_name: String,
+    /// For arrays, this is the datatype of the elements.
+    datatype: VariableDataType,
+    /// Arrays share their names with other variables, but can only be indexed and measured.
+    is_array: bool,
+}
+
+impl ScopedVariable {
+    fn describe_datatype(&self) -> String {
+        if self.is_array {
+            format!("{}[]", self.datatype)
+        } else {
+            self.datatype.to_string()
+        }
+    }
+}
+
+impl CheckerContext {
+    fn push_scope(&mut self) {
+        self.scope_count += 1;
+        self.scopes.push(Scope {
+            index: self.scope_count,
+            variables: HashMap::new(),
+        });
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Finds the variable that a name refers to, searching from the innermost scope outwards.
+    fn lookup_variable(&self, name: &str) -> Option<ScopedVariable> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.variables.get(name))
+            .cloned()
+    }
+
+    /// Declares a variable in the innermost scope, and returns its unique name. Variables at the top level
+    /// of the program keep their name, and every other variable gets the index of its scope tacked onto
+    /// the end (e.g. `x_3`), which can never clash with a name in the source code because identifiers
+    /// can't contain underscores.
+    fn declare_variable(
+        &mut self,
+        name: &str,
+        datatype: VariableDataType,
+        is_array: bool,
+        span: Span,
+    ) -> Option<String> {
+        let scope = self.scopes.last_mut().unwrap();
+
+        if let Some(existing) = scope.variables.get(name) {
+            let existing_datatype = existing.describe_datatype();
+
+            self.errors.push(
+                error(
+                    ErrorKind::Redeclaration,
+                    span,
+                    "Variable declared more than once in the same scope",
+                )
+                .with_label("already declared")
+                .with_note(format!(
+                    "`{name}` was already declared in this scope with datatype {existing_datatype}"
+                ))
+                .with_help(
+                    "Assign to the existing variable instead, or declare it inside of a block",
+                ),
+            );
+            return None;
+        }
+
+        let unique_name = match scope.index {
+            0 => name.to_string(),
+            index => format!("{name}_{index}"),
+        };
+
+        scope.variables.insert(
+            name.to_string(),
+            ScopedVariable {
+                unique_name: unique_name.clone(),
+                datatype,
+                is_array,
+            },
+        );
+
+        if is_array {
+            self.arrays.insert(unique_name.clone(), datatype);
+        } else {
+            self.variables.insert(unique_name.clone(), datatype);
+        }
+
+        Some(unique_name)
+    }
+}
+
+struct FunctionSignature {
+    return_datatype: VariableDataType,
+    parameters: Vec<(String, VariableDataType)>,
+}
+
+impl FunctionSignature {
+    /// Formats the signature the way it was written, e.g. `int add(int a, int b)`.
+    fn describe(&self, name: &str) -> String {
+        let parameters: Vec<String> = self
+            .parameters
+            .iter()
+            .map(|(name, datatype)| format!("{datatype} {name}"))
+            .collect();
+
+        format!("{} {name}({})", self.return_datatype, parameters.join(", "))
+    }
+}
+
+fn error(kind: ErrorKind, span: Span, message: &str) -> Diagnostic {
+    Diagnostic::new(kind, message).with_span(span)
+}
+
+/// Parses the source text and checks it, producing a program that is ready to be compiled or interpreted.
+/// If the program is invalid, every error that was found is returned.
+pub fn check_from_text(source_text: &str) -> Result<Program, CompileError> {
+    let file = match MedusaParser::parse(Rule::program, source_text) {
+        Err(e) => {
+            let span = match e.location {
+                InputLocation::Pos(position) => Span {
+                    start: position,
+                    end: position,
+                },
+                InputLocation::Span((start, end)) => Span { start, end },
+            };
+
+            return Err(error(ErrorKind::Syntax, span, "Syntax error")
+                .with_label(e.variant.message())
+                .into());
+        }
+        Ok(v) => v,
+    };
+
+    let mut context = CheckerContext {
+        scopes: vec![Scope {
+            index: 0,
+            variables: HashMap::new(),
+        }],
+        scope_count: 0,
+        variables: HashMap::new(),
+        arrays: HashMap::new(),
+        functions: HashMap::new(),
+        current_function: None,
+        loop_depth: 0,
+        errors: Vec::new(),
+    };
+
+    for pair in file.clone() {
+        if pair.as_rule() == Rule::function {
+            declare_function(pair, &mut context);
+        }
+    }
+
+    let mut statements = Vec::new();
+    let mut functions = Vec::new();
+
+    for pair in file {
+        match pair.as_rule() {
+            Rule::EOI => {}
+            Rule::function => {
+                if let Some(function) = check_function(pair, &mut context) {
+                    functions.push(function);
+                }
+            }
+            _ => {
+                if let Some(statement) = check_statement(pair, &mut context) {
+                    statements.push(statement);
+                }
+            }
+        }
+    }
+
+    if !context.errors.is_empty() {
+        return Err(CompileError {
+            diagnostics: context.errors,
+        });
+    }
+
+    Ok(Program {
+        statements,
+        variables: sorted_variables(context.variables),
+        arrays: sorted_variables(context.arrays),
+        functions,
+    })
+}
+
+/// Sorts the variables so that the same program always compiles to the same assembly.
+fn sorted_variables(
+    variables: HashMap<String, VariableDataType>,
+) -> Vec<(String, VariableDataType)> {
+    let mut variables: Vec<(String, VariableDataType)> = variables.into_iter().collect();
+    variables.sort_by(|a, b| a.0.cmp(&b.0));
+    variables
+}
+
+/// Records the name, return datatype, and parameters of a function so that it can be called from anywhere.
+fn declare_function(pair: Pair<Rule>, context: &mut CheckerContext) {
+    let mut pairs = pair.into_inner();
+
+    let return_datatype = datatype_from_str(pairs.next().unwrap().as_span().as_str());
+    let identifier = pairs.next().unwrap();
+    let name = identifier.as_span().as_str().to_string();
+
+    let parameters = pairs
+        .take_while(|pair| pair.as_rule() == Rule::parameter)
+        .map(|pair| {
+            let mut pairs = pair.into_inner();
+            let datatype = datatype_from_str(pairs.next().unwrap().as_span().as_str());
+            (
+                pairs.next().unwrap().as_span().as_str().to_string(),
+                datatype,
+            )
+        })
+        .collect();
+
+    if let Some(signature) = context.functions.get(&name) {
+        context.errors.push(
+            error(
+                ErrorKind::Redeclaration,
+                Span::from(identifier.as_span()),
+                "Function declared more than once",
+            )
+            .with_label("already declared")
+            .with_note(format!(
+                "`{name}` was first declared as `{}`",
+                signature.describe(&name)
+            )),
+        );
+        return;
+    }
+
+    context.functions.insert(
+        name,
+        FunctionSignature {
+            return_datatype,
+            parameters,
+        },
+    );
+}
+
+/// How tightly each operator binds, from the loosest to the tightest:
+///
+/// | Operators                        | Associativity |
+/// |----------------------------------|---------------|
+/// | `\|\|`                           | left          |
+/// | `&&`                             | left          |
+/// | `==` `!=` `<` `>` `<=` `>=`      | left          |
+/// | `..`                             | left          |
+/// | `+` `-`                          | left          |
+/// | `*` `/` `%`                      | left          |
+/// | `^`                              | right         |
+/// | casts, `!`, `-` and `+` (prefix) |               |
+///
+/// So `a - b - c` is `(a - b) - c`, `2 ^ 3 ^ 2` is `2 ^ (3 ^ 2)`, and `-x ^ 2` is `(-x) ^ 2`.
+static PRATT_PARSER: LazyLock<PrattParser<Rule>> = LazyLock::new(|| {
+    PrattParser::new()
+        .op(Op::infix(Rule::or, Assoc::Left))
+        .op(Op::infix(Rule::and, Assoc::Left))
+        .op(Op::infix(Rule::conditional_operator, Assoc::Left))
+        .op(Op::infix(Rule::concatenate, Assoc::Left))
+        .op(Op::infix(Rule::add, Assoc::Left) | Op::infix(Rule::subtract, Assoc::Left))
+        .op(Op::infix(Rule::multiply, Assoc::Left)
+            | Op::infix(Rule::divide, Assoc::Left)
+            | Op::infix(Rule::modulo, Assoc::Left))
+        .op(Op::infix(Rule::power, Assoc::Right))
+        .op(Op::prefix(Rule::cast)
+            | Op::prefix(Rule::not)
+            | Op::prefix(Rule::negate)
+            | Op::prefix(Rule::plus))
+});
+
+/// An expression whose operators have been grouped by PRATT_PARSER, but that hasn't been checked yet.
+enum GroupedExpression<'a> {
+    /// A literal, variable, call, etc. Parenthesized sub-expressions are left as a single Rule::expression
+    /// pair, which is grouped when it's checked.
+    Operand(Pair<'a, Rule>),
+    Prefix {
+        operator: Pair<'a, Rule>,
+        operand: Box<GroupedExpression<'a>>,
+    },
+    Infix {
+        operator: Pair<'a, Rule>,
+        left: Box<GroupedExpression<'a>>,
+        right: Box<GroupedExpression<'a>>,
+    },
+}
+
+fn group_expression(pair: Pair<Rule>) -> GroupedExpression {
+    PRATT_PARSER
+        .map_primary(GroupedExpression::Operand)
+        .map_prefix(|operator, operand| GroupedExpression::Prefix {
+            operator,
+            operand: Box::new(operand),
+        })
+        .map_infix(|left, operator, right| GroupedExpression::Infix {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+        .parse(pair.into_inner())
+}
+
+fn array_used_as_value(name: &str, span: Span) -> Diagnostic {
+    error(ErrorKind::TypeMismatch, span, "Array used as a value")
+        .with_label("this is an array")
+        .with_help(format!(
+            "Use one of its elements such as `{name}[0]`, or its length with `#{name}`"
+        ))
+}
+
+/// Finds the array that an identifier refers to, reporting an error if there isn't one. Returns the array's
+/// unique name and the datatype of its elements.
+fn lookup_array(
+    identifier: Pair<Rule>,
+    context: &mut CheckerContext,
+) -> Option<(String, VariableDataType)> {
+    let name = identifier.as_span().as_str();
+    let span = Span::from(identifier.as_span());
+
+    match context.lookup_variable(name) {
+        Some(variable) if variable.is_array => Some((variable.unique_name, variable.datatype)),
+        Some(variable) => {
+            context.errors.push(
+                error(ErrorKind::TypeMismatch, span, "Variable is not an array")
+                    .with_label(format!("this has datatype {}", variable.datatype))
+                    .with_help(format!(
+                        "Declare an array along with its length, e.g. `int[10] {name};`"
+                    )),
+            );
+            None
+        }
+        None => {
+            context.errors.push(
+                error(
+                    ErrorKind::UndeclaredVariable,
+                    span,
+                    "Array used but not declared",
+                )
+                .with_label("not declared")
+                .with_help(format!(
+                    "Declare the array along with its length, e.g. `int[10] {name};`"
+                )),
+            );
+            None
+        }
+    }
+}
+
+/// Checks an expression that's used as an array index or length, which has to be an int.
+fn check_int_expression(
+    pair: Pair<Rule>,
+    context: &mut CheckerContext,
+    message: &str,
+) -> Option<Expression> {
+    let expression = check_expression(pair, context)?;
+
+    if expression.datatype != VariableDataType::INT {
+        context.errors.push(
+            error(ErrorKind::TypeMismatch, expression.span, message)
+                .with_label(format!("this has datatype {}", expression.datatype))
+                .with_help("Use a cast such as (int) to convert the value"),
+        );
+        return None;
+    }
+
+    Some(expression)
+}
+
+fn datatype_from_str(name: &str) -> VariableDataType {
+    match name {
+        "int" | "(int)" => VariableDataType::INT,
+        "float" | "(float)" => VariableDataType::FLOAT,
+        "string" | "(string)" => VariableDataType::STRING,
+        "bool" => VariableDataType::BOOL,
+        _ => unreachable!(),
+    }
+}
+
+fn check_expression(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Expression> {
+    check_grouped_expression(group_expression(pair), context)
+}
+
+fn check_grouped_expression(
+    expression: GroupedExpression,
+    context: &mut CheckerContext,
+) -> Option<Expression> {
+    match expression {
+        GroupedExpression::Operand(token) => check_operand(token, context),
+        GroupedExpression::Prefix { operator, operand } => match *operand {
+            // A minus sign straight in front of a literal is folded into a single negative literal, so that it
+            // costs nothing at runtime. The digits of the smallest int are one too big for an int, so they're
+            // only allowed here.
+            GroupedExpression::Operand(token)
+                if operator.as_rule() == Rule::negate
+                    && matches!(token.as_rule(), Rule::int | Rule::float) =>
+            {
+                let literal = match token.as_rule() {
+                    Rule::int => check_int_literal(token, true, context)?,
+                    _ => check_operand(token, context)?,
+                };
+
+                let kind = match literal.kind {
+                    ExpressionKind::Int(value) => ExpressionKind::Int(value.wrapping_neg()),
+                    ExpressionKind::Float(value) => ExpressionKind::Float(-value),
+                    _ => unreachable!("only int and float literals are folded"),
+                };
+
+                Some(Expression {
+                    kind,
+                    datatype: literal.datatype,
+                    span: Span::from(operator.as_span()).to(literal.span),
+                })
+            }
+            operand => {
+                let operand = check_grouped_expression(operand, context)?;
+                check_prefix_operation(operator, operand, context)
+            }
+        },
+        GroupedExpression::Infix {
+            operator,
+            left,
+            right,
+        } => {
+            // Both operands are checked before giving up, so that the errors in each of them are reported
+            let left_operand = check_grouped_expression(*left, context);
+            let right_operand = check_grouped_expression(*right, context);
+
+            check_infix_operation(operator, left_operand?, right_operand?, context)
+        }
+    }
+}
+
+/// Replaces the escape sequences in a string literal (without its quotes) with the bytes they stand for.
+/// `start` is where the characters begin in the source text, so that a bad escape sequence can be pointed at.
+fn decode_string_literal(raw: &str, start: usize, context: &mut CheckerContext) -> Option<Vec<u8>> {
+    let mut value = Vec::with_capacity(raw.len());
+    let mut characters = raw.char_indices().peekable();
+
+    while let Some((index, character)) = characters.next() {
+        if character != '\\' {
+            value.extend_from_slice(character.encode_utf8(&mut [0; 4]).as_bytes());
+            continue;
+        }
+
+        // The grammar makes sure that a backslash is always followed by something
+        let (_, escape) = characters.next().unwrap();
+
+        let decoded = match escape {
+            'n' => Some('\n'),
+            't' => Some('\t'),
+            '\\' => Some('\\'),
+            '"' => Some('"'),
+            // Exactly two hex digits, for a single byte - which doesn't have to be a character on its own
+            'x' => {
+                let digits: String = (0..2)
+                    .map_while(|_| characters.next_if(|(_, x)| x.is_ascii_hexdigit()))
+                    .map(|(_, x)| x)
+                    .collect();
+
+                match u8::from_str_radix(&digits, 16) {
+                    Ok(byte) if digits.len() == 2 => {
+                        value.push(byte);
+                        continue;
+                    }
+                    _ => None,
+                }
+            }
+            // Between one and six hex digits in braces, for any Unicode character
+            'u' => {
+                let mut digits = String::new();
+
+                if characters.next_if(|(_, x)| *x == '{').is_some() {
+                    while let Some((_, digit)) = characters.next_if(|(_, x)| x.is_ascii_hexdigit())
+                    {
+                        digits.push(digit);
+                    }
+                }
+
+                let closed = characters.next_if(|(_, x)| *x == '}').is_some();
+
+                match u32::from_str_radix(&digits, 16) {
+                    Ok(code) if closed && digits.len() <= 6 => char::from_u32(code),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        match decoded {
+            Some(decoded) => value.extend_from_slice(decoded.encode_utf8(&mut [0; 4]).as_bytes()),
+            None => {
+                let end = characters.peek().map_or(raw.len(), |(end, _)| *end);
+
+                context.errors.push(
+                    error(
+                        ErrorKind::InvalidLiteral,
+                        Span {
+                            start: start + index,
+                            end: start + end,
+                        },
+                        "Invalid escape sequence",
+                    )
+                    .with_label("not a valid escape sequence")
+                    .with_help("Use one of \\n, \\t, \\\\, \\\", \\xNN, or \\u{NNNN}"),
+                );
+                return None;
+            }
+        }
+    }
+
+    Some(value)
+}
+
+/// Checks an int literal. Literals don't have a sign, so a negated literal can go one past the largest int,
+/// to 9223372036854775808 - it's stored as the smallest int, which negating leaves unchanged.
+fn check_int_literal(
+    token: Pair<Rule>,
+    negated: bool,
+    context: &mut CheckerContext,
+) -> Option<Expression> {
+    let span = Span::from(token.as_span());
+    let largest = i64::MAX as u64 + negated as u64;
+
+    match token.as_str().parse::<u64>() {
+        Ok(value) if value <= largest => Some(Expression {
+            kind: ExpressionKind::Int(value as i64),
+            datatype: VariableDataType::INT,
+            span,
+        }),
+        _ => {
+            context.errors.push(
+                error(
+                    ErrorKind::InvalidLiteral,
+                    span,
+                    "Integer literal is out of range",
+                )
+                .with_note(
+                    "ints are 64 bits wide, so they range from -9223372036854775808 to 9223372036854775807",
+                ),
+            );
+            None
+        }
+    }
+}
+
+fn check_operand(token: Pair<Rule>, context: &mut CheckerContext) -> Option<Expression> {
+    let span = Span::from(token.as_span());
+
+    match token.as_rule() {
+        Rule::int => check_int_literal(token, false, context),
+        Rule::float => match token.as_span().as_str().parse::<f64>() {
+            Ok(value) => Some(Expression {
+                kind: ExpressionKind::Float(value),
+                datatype: VariableDataType::FLOAT,
+                span,
+            }),
+            Err(_) => {
+                context.errors.push(error(
+                    ErrorKind::InvalidLiteral,
+                    span,
+                    "Invalid float literal",
+                ));
+                None
+            }
+        },
+        Rule::string => {
+            // Strip off the quotes
+            let raw_string = token.as_span().as_str();
+
+            let value = decode_string_literal(
+                &raw_string[1..raw_string.len() - 1],
+                span.start + 1,
+                context,
+            )?;
+
+            Some(Expression {
+                kind: ExpressionKind::String(value),
+                datatype: VariableDataType::STRING,
+                span,
+            })
+        }
+        Rule::boolean => Some(Expression {
+            kind: ExpressionKind::Bool(token.as_span().as_str() == "true"),
+            datatype: VariableDataType::BOOL,
+            span,
+        }),
+        Rule::expression => check_expression(token, context),
+        Rule::call => check_call(token, context),
+        Rule::element => {
+            let mut pairs = token.into_inner();
+
+            let array = lookup_array(pairs.next().unwrap(), context);
+            let index =
+                check_int_expression(pairs.next().unwrap(), context, "Array index is not an int");
+
+            match (array, index) {
+                (Some((name, datatype)), Some(index)) => Some(Expression {
+                    kind: ExpressionKind::Element {
+                        name,
+                        index: Box::new(index),
+                    },
+                    datatype,
+                    span,
+                }),
+                _ => None,
+            }
+        }
+        Rule::length => {
+            let array = lookup_array(token.into_inner().next().unwrap(), context);
+
+            array.map(|(name, _)| Expression {
+                kind: ExpressionKind::Length(name),
+                datatype: VariableDataType::INT,
+                span,
+            })
+        }
+        Rule::identifier => {
+            let name = token.as_span().as_str();
+
+            match context.lookup_variable(name) {
+                Some(variable) if variable.is_array => {
+                    context.errors.push(array_used_as_value(name, span));
+                    None
+                }
+                Some(variable) => Some(Expression {
+                    kind: ExpressionKind::Variable(variable.unique_name),
+                    datatype: variable.datatype,
+                    span,
+                }),
+                None => {
+                    context.errors.push(
+                        error(
+                            ErrorKind::UndeclaredVariable,
+                            span,
+                            "Variable used but not declared",
+                        )
+                        .with_label("not declared")
+                        .with_help(format!(
+                            "Declare the variable before using it, e.g. `int {name};`"
+                        )),
+                    );
+                    None
+                }
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn check_infix_operation(
+    token: Pair<Rule>,
+    left_operand: Expression,
+    right_operand: Expression,
+    context: &mut CheckerContext,
+) -> Option<Expression> {
+    match token.as_rule() {
+        Rule::add
+        | Rule::subtract
+        | Rule::multiply
+        | Rule::divide
+        | Rule::modulo
+        | Rule::power
+        | Rule::concatenate => {
+            let operator = match token.as_rule() {
+                Rule::add => BinaryOperator::Add,
+                Rule::subtract => BinaryOperator::Subtract,
+                Rule::multiply => BinaryOperator::Multiply,
+                Rule::divide => BinaryOperator::Divide,
+                Rule::modulo => BinaryOperator::Modulo,
+                Rule::power => BinaryOperator::Power,
+                Rule::concatenate => BinaryOperator::Concatenate,
+                _ => unreachable!(),
+            };
+
+            let span = left_operand.span.to(right_operand.span);
+            let label = format!(
+                "{} {} {}",
+                left_operand.datatype,
+                operator.as_str(),
+                right_operand.datatype
+            );
+
+            // Make sure the semantic rules are not being violated (i.e. correct datatypes on all variables)
+            let diagnostic = if operator == BinaryOperator::Concatenate {
+                if left_operand.datatype != VariableDataType::STRING
+                    || right_operand.datatype != VariableDataType::STRING
+                {
+                    Some(
+                        error(ErrorKind::TypeMismatch, span, "Concatenation on non-string")
+                            .with_label(label)
+                            .with_help("Convert the operands to strings first with (string)"),
+                    )
+                } else {
+                    None
+                }
+            } else if left_operand.datatype != right_operand.datatype {
+                Some(
+                    error(ErrorKind::TypeMismatch, span, "Datatypes don't match")
+                        .with_label(label)
+                        .with_help("Use a cast such as (float) to convert one of the operands"),
+                )
+            } else if left_operand.datatype == VariableDataType::STRING {
+                Some(
+                    error(
+                        ErrorKind::TypeMismatch,
+                        span,
+                        "Math operation on non-number",
+                    )
+                    .with_label(label)
+                    .with_help("Strings are joined together with .."),
+                )
+            } else if left_operand.datatype == VariableDataType::BOOL {
+                Some(
+                    error(
+                        ErrorKind::TypeMismatch,
+                        span,
+                        "Math operation on non-number",
+                    )
+                    .with_label(label)
+                    .with_help("Bools are combined with && and ||"),
+                )
+            } else if operator == BinaryOperator::Modulo
+                && left_operand.datatype == VariableDataType::FLOAT
+            {
+                Some(
+                    error(
+                        ErrorKind::TypeMismatch,
+                        span,
+                        "Modulo is not supported for floats",
+                    )
+                    .with_label(label)
+                    .with_help("Convert the operands to ints first with (int)"),
+                )
+            } else {
+                None
+            };
+
+            if let Some(diagnostic) = diagnostic {
+                context.errors.push(diagnostic);
+                return None;
+            }
+
+            Some(Expression {
+                datatype: left_operand.datatype,
+                span,
+                kind: ExpressionKind::Binary {
+                    operator,
+                    left: Box::new(left_operand),
+                    right: Box::new(right_operand),
+                },
+            })
+        }
+        Rule::conditional_operator => {
+            let operator = match token.as_span().as_str() {
+                ">" => ConditionalOperator::Greater,
+                "<" => ConditionalOperator::Less,
+                ">=" => ConditionalOperator::GreaterOrEqual,
+                "<=" => ConditionalOperator::LessOrEqual,
+                "==" => ConditionalOperator::Equal,
+                "!=" => ConditionalOperator::NotEqual,
+                _ => unreachable!(),
+            };
+
+            let span = left_operand.span.to(right_operand.span);
+            let label = format!(
+                "{} {} {}",
+                left_operand.datatype,
+                operator.as_str(),
+                right_operand.datatype
+            );
+
+            let diagnostic = if left_operand.datatype != right_operand.datatype {
+                Some(
+                    error(
+                        ErrorKind::TypeMismatch,
+                        span,
+                        "Datatypes in comparison do not match",
+                    )
+                    .with_label(label)
+                    .with_help("Use a cast such as (float) to convert one side of the comparison"),
+                )
+            } else if left_operand.datatype == VariableDataType::BOOL
+                && operator != ConditionalOperator::Equal
+                && operator != ConditionalOperator::NotEqual
+            {
+                Some(
+                    error(ErrorKind::TypeMismatch, span, "Bools can't be ordered")
+                        .with_label(label)
+                        .with_help("Bools can only be compared with == and !="),
+                )
+            } else {
+                None
+            };
+
+            if let Some(diagnostic) = diagnostic {
+                context.errors.push(diagnostic);
+                return None;
+            }
+
+            Some(Expression {
+                datatype: VariableDataType::BOOL,
+                span,
+                kind: ExpressionKind::Comparison {
+                    operator,
+                    left: Box::new(left_operand),
+                    right: Box::new(right_operand),
+                },
+            })
+        }
+        Rule::and | Rule::or => {
+            let operator = match token.as_rule() {
+                Rule::and => LogicalOperator::And,
+                _ => LogicalOperator::Or,
+            };
+
+            let span = left_operand.span.to(right_operand.span);
+
+            if left_operand.datatype != VariableDataType::BOOL
+                || right_operand.datatype != VariableDataType::BOOL
+            {
+                context.errors.push(
+                    error(
+                        ErrorKind::TypeMismatch,
+                        span,
+                        "Logical operation on non-bool",
+                    )
+                    .with_label(format!(
+                        "{} {} {}",
+                        left_operand.datatype,
+                        operator.as_str(),
+                        right_operand.datatype
+                    ))
+                    .with_help("Compare the value to make a bool, e.g. `x != 0`"),
+                );
+                return None;
+            }
+
+            Some(Expression {
+                datatype: VariableDataType::BOOL,
+                span,
+                kind: ExpressionKind::Logical {
+                    operator,
+                    left: Box::new(left_operand),
+                    right: Box::new(right_operand),
+                },
+            })
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn check_prefix_operation(
+    token: Pair<Rule>,
+    operand: Expression,
+    context: &mut CheckerContext,
+) -> Option<Expression> {
+    let span = Span::from(token.as_span());
+
+    match token.as_rule() {
+        Rule::not => {
+            let span = span.to(operand.span);
+
+            if operand.datatype != VariableDataType::BOOL {
+                context.errors.push(
+                    error(
+                        ErrorKind::TypeMismatch,
+                        span,
+                        "Logical operation on non-bool",
+                    )
+                    .with_label(format!("! {}", operand.datatype))
+                    .with_help("Compare the value to make a bool, e.g. `x == 0`"),
+                );
+                return None;
+            }
+
+            Some(Expression {
+                datatype: VariableDataType::BOOL,
+                span,
+                kind: ExpressionKind::Not(Box::new(operand)),
+            })
+        }
+        Rule::negate | Rule::plus => {
+            let span = span.to(operand.span);
+
+            if operand.datatype != VariableDataType::INT
+                && operand.datatype != VariableDataType::FLOAT
+            {
+                let help = if operand.datatype == VariableDataType::BOOL {
+                    "Bools are negated with !"
+                } else {
+                    "Convert the value to a number first with (int) or (float)"
+                };
+
+                context.errors.push(
+                    error(
+                        ErrorKind::TypeMismatch,
+                        span,
+                        "Math operation on non-number",
+                    )
+                    .with_label(format!("{} {}", token.as_str(), operand.datatype))
+                    .with_help(help),
+                );
+                return None;
+            }
+
+            // A plus sign doesn't change anything. Negative literals have already been folded (see
+            // check_grouped_expression), so everything else is negated at runtime, where it's checked for
+            // overflow along with the rest of the arithmetic.
+            let datatype = operand.datatype;
+            let kind = match token.as_rule() {
+                Rule::plus => operand.kind,
+                _ => ExpressionKind::Negate(Box::new(operand)),
+            };
+
+            Some(Expression {
+                datatype,
+                span,
+                kind,
+            })
+        }
+        Rule::cast => {
+            let datatype = datatype_from_str(token.as_span().as_str());
+            let span = span.to(operand.span);
+
+            // There's no sensible number for true or false, so bools can only become strings
+            if operand.datatype == VariableDataType::BOOL && datatype != VariableDataType::STRING {
+                context.errors.push(
+                    error(
+                        ErrorKind::TypeMismatch,
+                        span,
+                        "Bools can only be cast to string",
+                    )
+                    .with_label(format!("bool cast to {datatype}"))
+                    .with_help("Use an if statement to pick a number for each value"),
+                );
+                return None;
+            }
+
+            Some(Expression {
+                datatype,
+                span,
+                kind: ExpressionKind::Cast(Box::new(operand)),
+            })
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn check_call(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Expression> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let identifier = pairs.next().unwrap();
+    let name = identifier.as_span().as_str().to_string();
+
+    // The arguments are checked even if the function doesn't exist, so that their errors are reported too
+    let arguments: Vec<Option<Expression>> =
+        pairs.map(|pair| check_expression(pair, context)).collect();
+
+    let Some(signature) = context.functions.get(&name) else {
+        context.errors.push(
+            error(
+                ErrorKind::UndeclaredFunction,
+                Span::from(identifier.as_span()),
+                "Function called but not declared",
+            )
+            .with_label("not declared"),
+        );
+        return None;
+    };
+
+    let arguments: Vec<Expression> = arguments.into_iter().collect::<Option<_>>()?;
+
+    if arguments.len() != signature.parameters.len() {
+        context.errors.push(
+            error(
+                ErrorKind::WrongArgumentCount,
+                span,
+                "Wrong number of arguments",
+            )
+            .with_label(format!(
+                "expected {} arguments, found {}",
+                signature.parameters.len(),
+                arguments.len()
+            ))
+            .with_note(format!(
+                "`{name}` is declared as `{}`",
+                signature.describe(&name)
+            )),
+        );
+        return None;
+    }
+
+    let mut valid = true;
+
+    for (argument, (parameter, datatype)) in arguments.iter().zip(signature.parameters.iter()) {
+        if argument.datatype != *datatype {
+            context.errors.push(
+                error(
+                    ErrorKind::TypeMismatch,
+                    argument.span,
+                    "Mismatched datatypes on argument",
+                )
+                .with_label(format!("this has datatype {}", argument.datatype))
+                .with_note(format!(
+                    "parameter `{parameter}` of `{name}` has datatype {datatype}"
+                ))
+                .with_help(format!(
+                    "Use a cast such as ({datatype}) to convert the value"
+                )),
+            );
+            valid = false;
+        }
+    }
+
+    if !valid {
+        return None;
+    }
+
+    Some(Expression {
+        datatype: signature.return_datatype,
+        span,
+        kind: ExpressionKind::Call { name, arguments },
+    })
+}
+
+fn check_declaration(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Declaration> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let datatype = datatype_from_str(pairs.next().unwrap().as_span().as_str());
+
+    // There are three options for a declaration:
+    //  1. Datatype and identifier
+    //  2. Datatype and assignment
+    //  3. Datatype and input statement
+    // We need to figure out which option is being used
+
+    let pair = pairs.next().unwrap();
+
+    // Peek into the assignment or input statement so we can grab the identifier of the variable before
+    // checking the rest of the statement.
+    let identifier = match pair.as_rule() {
+        Rule::identifier => pair.clone(),
+        _ => pair.clone().into_inner().peek().unwrap(),
+    };
+
+    // The starting value is checked before the variable is declared, so that `int x = x + 1;` refers to an
+    // `x` from an outer scope rather than to the new variable
+    let initializer = match pair.as_rule() {
+        Rule::assignment => Some(check_expression(
+            pair.clone().into_inner().nth(1).unwrap(),
+            context,
+        )),
+        _ => None,
+    };
+
+    // The variable is declared even if its value has an error, so that later uses of it don't report
+    // errors of their own
+    let name = context.declare_variable(
+        identifier.as_span().as_str(),
+        datatype,
+        false,
+        Span::from(identifier.as_span()),
+    )?;
+
+    let value = match pair.as_rule() {
+        Rule::identifier => DeclarationValue::None,
+        Rule::assignment => DeclarationValue::Assignment(Assignment {
+            name: name.clone(),
+            value: check_assigned_datatype(
+                identifier.as_span().as_str(),
+                datatype,
+                initializer.flatten()?,
+                context,
+            )?,
+            span: Span::from(pair.as_span()),
+        }),
+        _ => DeclarationValue::Input(check_input(pair, context)?),
+    };
+
+    Some(Declaration {
+        datatype,
+        name,
+        value,
+        span,
+    })
+}
+
+/// Finds the variable that an assignment stores into, reporting an error if there isn't one.
+fn lookup_assigned_variable(
+    identifier: &Pair<Rule>,
+    context: &mut CheckerContext,
+) -> Option<ScopedVariable> {
+    let name = identifier.as_span().as_str();
+    let variable = context.lookup_variable(name);
+
+    if variable.is_none() {
+        context.errors.push(
+            error(
+                ErrorKind::UndeclaredVariable,
+                Span::from(identifier.as_span()),
+                "Variable assigned to but not declared",
+            )
+            .with_label("not declared")
+            .with_help(format!(
+                "Declare the variable along with its datatype, e.g. `int {name} = ...;`"
+            )),
+        );
+    }
+
+    variable
+}
+
+fn check_assignment(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Assignment> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let identifier = pairs.next().unwrap();
+    let name = identifier.as_span().as_str().to_string();
+
+    let variable = lookup_assigned_variable(&identifier, context);
+
+    // The value is checked even if the variable doesn't exist, so that its errors are reported too
+    let value = check_expression(pairs.next().unwrap(), context)?;
+    let variable = variable?;
+
+    if variable.is_array {
+        context
+            .errors
+            .push(array_used_as_value(&name, Span::from(identifier.as_span())));
+        return None;
+    }
+
+    Some(Assignment {
+        value: check_assigned_datatype(&name, variable.datatype, value, context)?,
+        name: variable.unique_name,
+        span,
+    })
+}
+
+/// Makes sure that a value being stored into a variable has the variable's datatype.
+fn check_assigned_datatype(
+    name: &str,
+    datatype: VariableDataType,
+    value: Expression,
+    context: &mut CheckerContext,
+) -> Option<Expression> {
+    if value.datatype != datatype {
+        context.errors.push(
+            error(
+                ErrorKind::TypeMismatch,
+                value.span,
+                "Mismatched datatypes on assignment",
+            )
+            .with_label(format!("this has datatype {}", value.datatype))
+            .with_note(format!("`{name}` was declared with datatype {datatype}"))
+            .with_help(format!(
+                "Use a cast such as ({datatype}) to convert the value"
+            )),
+        );
+        return None;
+    }
+
+    Some(value)
+}
+
+/// Checks `x += y` or `x++`, which become the assignments `x = x + y` and `x = x + 1`. The operation is
+/// checked just like it would be in an expression, so the value has to have the variable's datatype.
+fn check_compound_assignment(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Assignment> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let identifier = pairs.next().unwrap();
+    let name = identifier.as_span().as_str().to_string();
+    let variable = lookup_assigned_variable(&identifier, context);
+
+    // The operator pair (e.g. `+=`) holds the binary operator that it applies (e.g. `+`)
+    let operator_pair = pairs.next().unwrap();
+    let operator_span = Span::from(operator_pair.as_span());
+    let operator_text = operator_pair.as_str();
+    let operator = operator_pair.into_inner().next().unwrap();
+
+    // The value is checked even if the variable doesn't exist, so that its errors are reported too. `x++`
+    // doesn't have one.
+    let value = pairs.next().map(|pair| check_expression(pair, context));
+    let variable = variable?;
+
+    if variable.is_array {
+        context
+            .errors
+            .push(array_used_as_value(&name, Span::from(identifier.as_span())));
+        return None;
+    }
+
+    let datatype = variable.datatype;
+
+    let value = match value {
+        Some(value) => value?,
+        None => {
+            let kind = match datatype {
+                VariableDataType::INT => ExpressionKind::Int(1),
+                VariableDataType::FLOAT => ExpressionKind::Float(1.0),
+                _ => {
+                    context.errors.push(
+                        error(
+                            ErrorKind::TypeMismatch,
+                            span,
+                            "Math operation on non-number",
+                        )
+                        .with_label(format!("{datatype}{operator_text}"))
+                        .with_note(format!("`{name}` was declared with datatype {datatype}")),
+                    );
+                    return None;
+                }
+            };
+
+            Expression {
+                kind,
+                datatype,
+                span: operator_span,
+            }
+        }
+    };
+
+    let variable_value = Expression {
+        kind: ExpressionKind::Variable(variable.unique_name.clone()),
+        datatype,
+        span: Span::from(identifier.as_span()),
+    };
+
+    Some(Assignment {
+        name: variable.unique_name,
+        value: check_infix_operation(operator, variable_value, value, context)?,
+        span,
+    })
+}
+
+fn check_array_declaration(
+    pair: Pair<Rule>,
+    context: &mut CheckerContext,
+) -> Option<ArrayDeclaration> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let element_datatype = datatype_from_str(pairs.next().unwrap().as_span().as_str());
+
+    // The length is checked before the array is declared, so it can't refer to the array itself
+    let length = check_int_expression(pairs.next().unwrap(), context, "Array length is not an int");
+
+    let identifier = pairs.next().unwrap();
+    let name = context.declare_variable(
+        identifier.as_span().as_str(),
+        element_datatype,
+        true,
+        Span::from(identifier.as_span()),
+    )?;
+
+    Some(ArrayDeclaration {
+        element_datatype,
+        name,
+        length: length?,
+        span,
+    })
+}
+
+fn check_element_assignment(
+    pair: Pair<Rule>,
+    context: &mut CheckerContext,
+) -> Option<ElementAssignment> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let mut element_pairs = pairs.next().unwrap().into_inner();
+    let identifier = element_pairs.next().unwrap();
+    let source_name = identifier.as_span().as_str().to_string();
+
+    let array = lookup_array(identifier, context);
+    let index = check_int_expression(
+        element_pairs.next().unwrap(),
+        context,
+        "Array index is not an int",
+    );
+    let value = check_expression(pairs.next().unwrap(), context);
+
+    let ((name, datatype), index, value) = (array?, index?, value?);
+
+    if value.datatype != datatype {
+        context.errors.push(
+            error(
+                ErrorKind::TypeMismatch,
+                value.span,
+                "Mismatched datatypes on assignment",
+            )
+            .with_label(format!("this has datatype {}", value.datatype))
+            .with_note(format!(
+                "`{source_name}` was declared as an array of {datatype}"
+            ))
+            .with_help(format!(
+                "Use a cast such as ({datatype}) to convert the value"
+            )),
+        );
+        return None;
+    }
+
+    Some(ElementAssignment {
+        name,
+        index,
+        value,
+        span,
+    })
+}
+
+fn check_output(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Output> {
+    let span = Span::from(pair.as_span());
+    let expression_pair = pair.into_inner().next().unwrap();
+
+    Some(Output {
+        value: check_expression(expression_pair, context)?,
+        span,
+    })
+}
+
+fn check_input(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Input> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+    let identifier = pairs.next().unwrap();
+    let name = identifier.as_span().as_str().to_string();
+
+    let Some(variable) = context.lookup_variable(&name) else {
+        context.errors.push(
+            error(
+                ErrorKind::UndeclaredVariable,
+                Span::from(identifier.as_span()),
+                "Variable does not exist for input",
+            )
+            .with_label("not declared")
+            .with_help(format!(
+                "Declare the variable while reading into it, e.g. `int {name} <- @;`"
+            )),
+        );
+        return None;
+    };
+
+    if variable.is_array {
+        context
+            .errors
+            .push(array_used_as_value(&name, Span::from(identifier.as_span())));
+        return None;
+    }
+
+    let (unique_name, datatype) = (variable.unique_name, variable.datatype);
+
+    if datatype == VariableDataType::BOOL {
+        context.errors.push(
+            error(
+                ErrorKind::Unsupported,
+                span,
+                "Reading a bool from input is not supported",
+            )
+            .with_help(format!(
+                "Read a string instead and compare it, e.g. `{name} = answer == \"yes\";`"
+            )),
+        );
+        return None;
+    }
+
+    let else_body = match pairs.next() {
+        Some(else_pair) => {
+            if datatype == VariableDataType::STRING {
+                context.errors.push(
+                    error(
+                        ErrorKind::InvalidStatement,
+                        Span::from(else_pair.as_span()),
+                        "Reading a string can't fail",
+                    )
+                    .with_label("this never runs")
+                    .with_help("Only reading an int or a float needs an else block"),
+                );
+                return None;
+            }
+
+            Some(check_scoped_block(else_pair.into_inner(), context)?)
+        }
+        None => None,
+    };
+
+    Some(Input {
+        name: unique_name,
+        datatype,
+        else_body,
+        span,
+    })
+}
+
+fn check_condition(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Expression> {
+    let condition = check_expression(pair.into_inner().next().unwrap(), context)?;
+
+    if condition.datatype != VariableDataType::BOOL {
+        context.errors.push(
+            error(
+                ErrorKind::TypeMismatch,
+                condition.span,
+                "Condition is not a bool",
+            )
+            .with_label(format!("this has datatype {}", condition.datatype))
+            .with_help("Compare the value to make a bool, e.g. `x != 0`"),
+        );
+        return None;
+    }
+
+    Some(condition)
+}
+
+/// Checks every statement in a block, even the ones after a statement with an error.
+fn check_block<'a>(
+    pairs: impl Iterator<Item = Pair<'a, Rule>>,
+    context: &mut CheckerContext,
+) -> Option<Vec<Statement>> {
+    let statements: Vec<Option<Statement>> =
+        pairs.map(|pair| check_statement(pair, context)).collect();
+
+    statements.into_iter().collect()
+}
+
+/// Checks a block in a scope of its own, so that the variables declared in it can't be seen afterwards.
+fn check_scoped_block<'a>(
+    pairs: impl Iterator<Item = Pair<'a, Rule>>,
+    context: &mut CheckerContext,
+) -> Option<Vec<Statement>> {
+    context.push_scope();
+    let statements = check_block(pairs, context);
+    context.pop_scope();
+
+    statements
+}
+
+fn check_if(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<If> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let condition = check_condition(pairs.next().unwrap(), context);
+
+    let mut pairs = pairs.peekable();
+
+    let mut body_pairs = Vec::new();
+    while let Some(pair) =
+        pairs.next_if(|pair| pair.as_rule() != Rule::else_if && pair.as_rule() != Rule::else_)
+    {
+        body_pairs.push(pair);
+    }
+
+    let body = check_scoped_block(body_pairs.into_iter(), context);
+
+    let mut else_ifs = Vec::new();
+    let mut else_body = Some(None);
+
+    // The else ifs come after the body and the else statement always comes last, so everything
+    // is checked in the order it was written
+    for pair in pairs {
+        match pair.as_rule() {
+            Rule::else_if => else_ifs.push(check_else_if(pair, context)),
+            _ => else_body = check_scoped_block(pair.into_inner(), context).map(Some),
+        }
+    }
+
+    let else_ifs: Option<Vec<ElseIf>> = else_ifs.into_iter().collect();
+
+    Some(If {
+        condition: condition?,
+        body: body?,
+        else_ifs: else_ifs?,
+        else_body: else_body?,
+        span,
+    })
+}
+
+fn check_else_if(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<ElseIf> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let condition = check_condition(pairs.next().unwrap(), context);
+    let body = check_scoped_block(pairs, context);
+
+    Some(ElseIf {
+        condition: condition?,
+        body: body?,
+        span,
+    })
+}
+
+fn check_forloop(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<ForLoop> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    // A variable declared by the initializer belongs to the loop, so it can't be seen after the loop ends
+    context.push_scope();
+
+    let first_pair = pairs.next().unwrap();
+
+    let initializer = match first_pair.as_rule() {
+        Rule::declaration => check_declaration(first_pair, context)
+            .map(|x| Some(Box::new(Statement::Declaration(x)))),
+        Rule::assignment => {
+            check_assignment(first_pair, context).map(|x| Some(Box::new(Statement::Assignment(x))))
+        }
+        Rule::identifier => {
+            // Do absolutely nothing, because this language is weird >:(
+            Some(None)
+        }
+        _ => unreachable!(),
+    };
+
+    let condition = check_condition(pairs.next().unwrap(), context);
+    let step_pair = pairs.next().unwrap();
+    let step = match step_pair.as_rule() {
+        Rule::assignment => check_assignment(step_pair, context),
+        _ => check_compound_assignment(step_pair, context),
+    };
+
+    context.loop_depth += 1;
+    let body = check_scoped_block(pairs, context);
+    context.loop_depth -= 1;
+
+    context.pop_scope();
+
+    Some(ForLoop {
+        initializer: initializer?,
+        condition: condition?,
+        step: step?,
+        body: body?,
+        span,
+    })
+}
+
+fn check_while(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<While> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner();
+
+    let condition = check_condition(pairs.next().unwrap(), context);
+
+    context.loop_depth += 1;
+    let body = check_scoped_block(pairs, context);
+    context.loop_depth -= 1;
+
+    Some(While {
+        condition: condition?,
+        body: body?,
+        span,
+    })
+}
+
+fn check_function(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Function> {
+    let span = Span::from(pair.as_span());
+    let mut pairs = pair.into_inner().peekable();
+
+    let return_datatype = datatype_from_str(pairs.next().unwrap().as_span().as_str());
+    let identifier = pairs.next().unwrap();
+    let name = identifier.as_span().as_str().to_string();
+
+    // A function can only see its own parameters and variables, which all live in the function's scope
+    let outer_scopes = std::mem::take(&mut context.scopes);
+    let outer_variables = std::mem::take(&mut context.variables);
+    let outer_arrays = std::mem::take(&mut context.arrays);
+    context.current_function = Some((name.clone(), return_datatype));
+    let outer_loop_depth = std::mem::take(&mut context.loop_depth);
+    context.push_scope();
+
+    let mut parameters = Vec::new();
+
+    while let Some(pair) = pairs.next_if(|pair| pair.as_rule() == Rule::parameter) {
+        let parameter_span = Span::from(pair.as_span());
+        let mut parameter_pairs = pair.into_inner();
+
+        let datatype = datatype_from_str(parameter_pairs.next().unwrap().as_span().as_str());
+        let parameter_name = parameter_pairs.next().unwrap().as_span().as_str();
+
+        // A parameter with a duplicate name still takes up an argument, so it's kept in the list
+        let unique_name = context
+            .declare_variable(parameter_name, datatype, false, parameter_span)
+            .unwrap_or_else(|| parameter_name.to_string());
+
+        parameters.push(Parameter {
+            name: unique_name,
+            datatype,
+            span: parameter_span,
+        });
+    }
+
+    let body = check_block(pairs, context);
+
+    let variables = std::mem::replace(&mut context.variables, outer_variables);
+    let arrays = std::mem::replace(&mut context.arrays, outer_arrays);
+    context.scopes = outer_scopes;
+    context.current_function = None;
+    context.loop_depth = outer_loop_depth;
+
+    let body = body?;
+
+    if !always_returns(&body) {
+        context.errors.push(
+            error(
+                ErrorKind::MissingReturn,
+                Span::from(identifier.as_span()),
+                "Function might not return a value",
+            )
+            .with_label(format!(
+                "this must return a value of type {return_datatype}"
+            ))
+            .with_help("Add a return statement to the end of the function"),
+        );
+        return None;
+    }
+
+    Some(Function {
+        name,
+        return_datatype,
+        parameters,
+        body,
+        variables: sorted_variables(variables),
+        arrays: sorted_variables(arrays),
+        span,
+    })
+}
+
+/// Returns true if running the statements is guaranteed to hit a return statement.
+fn always_returns(statements: &[Statement]) -> bool {
+    statements.iter().any(|statement| match statement {
+        Statement::Return(_) => true,
+        // Every branch has to return, including the else statement
+        Statement::If(if_statement) => {
+            always_returns(&if_statement.body)
+                && if_statement
+                    .else_ifs
+                    .iter()
+                    .all(|else_if| always_returns(&else_if.body))
+                && if_statement
+                    .else_body
+                    .as_ref()
+                    .is_some_and(|else_body| always_returns(else_body))
+        }
+        _ => false,
+    })
+}
+
+fn check_return(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Return> {
+    let span = Span::from(pair.as_span());
+    let value = check_expression(pair.into_inner().next().unwrap(), context);
+
+    let Some((name, return_datatype)) = context.current_function.clone() else {
+        context.errors.push(
+            error(
+                ErrorKind::InvalidStatement,
+                span,
+                "Return outside of a function",
+            )
+            .with_help("The program ends on its own after its last statement"),
+        );
+        return None;
+    };
+
+    let value = value?;
+
+    if value.datatype != return_datatype {
+        context.errors.push(
+            error(
+                ErrorKind::TypeMismatch,
+                value.span,
+                "Mismatched datatypes on return",
+            )
+            .with_label(format!("this has datatype {}", value.datatype))
+            .with_note(format!("`{name}` returns {return_datatype}"))
+            .with_help(format!(
+                "Use a cast such as ({return_datatype}) to convert the value"
+            )),
+        );
+        return None;
+    }
+
+    Some(Return { value, span })
+}
+
+/// Checks `break` or `continue`, which can only be used inside of a loop.
+fn check_loop_jump(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Statement> {
+    let span = Span::from(pair.as_span());
+
+    if context.loop_depth == 0 {
+        let keyword = pair.as_str();
+
+        context.errors.push(
+            error(
+                ErrorKind::InvalidStatement,
+                span,
+                &format!("`{keyword}` outside of a loop"),
+            )
+            .with_help(format!(
+                "`{keyword}` can only be used inside of a for or while loop"
+            )),
+        );
+        return None;
+    }
+
+    Some(match pair.as_rule() {
+        Rule::break_ => Statement::Break(span),
+        _ => Statement::Continue(span),
+    })
+}
+
+fn check_statement(pair: Pair<Rule>, context: &mut CheckerContext) -> Option<Statement> {
+    Some(match pair.as_rule() {
+        Rule::declaration => Statement::Declaration(check_declaration(pair, context)?),
+        Rule::array_declaration => {
+            Statement::ArrayDeclaration(check_array_declaration(pair, context)?)
+        }
+        Rule::assignment => Statement::Assignment(check_assignment(pair, context)?),
+        Rule::compound_assignment | Rule::increment => {
+            Statement::Assignment(check_compound_assignment(pair, context)?)
+        }
+        Rule::element_assignment => {
+            Statement::ElementAssignment(check_element_assignment(pair, context)?)
+        }
+        Rule::output => Statement::Output(check_output(pair, context)?),
+        Rule::input => Statement::Input(check_input(pair, context)?),
+        Rule::if_ => Statement::If(check_if(pair, context)?),
+        Rule::forloop => Statement::ForLoop(check_forloop(pair, context)?),
+        Rule::while_ => Statement::While(check_while(pair, context)?),
+        Rule::return_ => Statement::Return(check_return(pair, context)?),
+        Rule::break_ | Rule::continue_ => check_loop_jump(pair, context)?,
+        _ => unreachable!(),
+    })
+}
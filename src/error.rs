@@ -0,0 +1,261 @@
+// Errors produced while compiling or running a Medusa program. A CompileError holds one or more diagnostics,
+// so the checker can report every problem in a file at once. Every diagnostic carries a kind, so callers can
+// tell the different failures apart, and diagnostics that come from the source text also carry the span of
+// the offending code, which `render` turns into a snippet with the span underlined.
+
+use crate::ast::Span;
+use crate::VariableDataType;
+
+/// What went wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The source text doesn't match the grammar.
+    Syntax,
+    /// A variable was used before it was declared.
+    UndeclaredVariable,
+    /// A function was called, but never declared.
+    UndeclaredFunction,
+    /// Something with the same name has already been declared.
+    Redeclaration,
+    /// A function was called with the wrong number of arguments.
+    WrongArgumentCount,
+    /// A function might reach the end of its body without returning a value.
+    MissingReturn,
+    /// A statement was used somewhere it isn't allowed (e.g. return outside of a function).
+    InvalidStatement,
+    /// An operation was applied to values of the wrong datatype.
+    TypeMismatch,
+    /// A literal can't be represented (e.g. an integer that doesn't fit in 64 bits).
+    InvalidLiteral,
+    /// The operation is valid Medusa, but isn't supported by the compiler yet.
+    Unsupported,
+    /// The program failed while it was being interpreted.
+    Runtime(RuntimeError),
+    /// Reading or writing a file (or the interpreter's input and output) failed.
+    Io,
+    /// The assembler or linker couldn't be found, or failed.
+    Toolchain,
+    /// The compiler was invoked incorrectly (e.g. an unknown target).
+    Usage,
+}
+
+/// The errors that stop a program while it's running. Each one exits with its own code, so that whoever ran
+/// the program can tell them apart - the interpreter exits with the same codes as a compiled program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeError {
+    IndexOutOfBounds,
+    NegativeArrayLength,
+    DivisionByZero,
+    /// A number was read from input, but the input wasn't a valid number of the variable's datatype.
+    InvalidInput(VariableDataType),
+    /// Only checked for when the program was compiled with overflow checks.
+    IntegerOverflow,
+}
+
+impl RuntimeError {
+    pub fn message(&self) -> &'static str {
+        match self {
+            RuntimeError::IndexOutOfBounds => "Array index out of bounds",
+            RuntimeError::NegativeArrayLength => "Array length is negative",
+            RuntimeError::DivisionByZero => "Division by zero",
+            RuntimeError::InvalidInput(VariableDataType::FLOAT) => "Input is not a valid float",
+            RuntimeError::InvalidInput(_) => "Input is not a valid int",
+            RuntimeError::IntegerOverflow => "Integer overflow",
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            RuntimeError::IndexOutOfBounds => 2,
+            RuntimeError::NegativeArrayLength => 3,
+            RuntimeError::DivisionByZero => 4,
+            RuntimeError::InvalidInput(_) => 5,
+            RuntimeError::IntegerOverflow => 6,
+        }
+    }
+}
+
+/// A single problem, along with everything needed to explain it to the user.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// The source code that caused the error, if the error came from the source text.
+    pub span: Option<Span>,
+    /// A short explanation printed next to the underlined source code.
+    pub label: Option<String>,
+    pub notes: Vec<String>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            kind,
+            message: message.into(),
+            span: None,
+            label: None,
+            notes: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn with_span(mut self, span: Span) -> Diagnostic {
+        self.span = Some(span);
+        self
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Diagnostic {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Diagnostic {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Diagnostic {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Returns the line and column that the span starts and ends at, if this diagnostic has a span.
+    pub fn range(&self, source_text: &str) -> Option<(Position, Position)> {
+        self.span.map(|span| {
+            (
+                Position::from_offset(source_text, span.start),
+                Position::from_offset(source_text, span.end),
+            )
+        })
+    }
+
+    /// Formats the diagnostic for the terminal, with the offending source code underlined:
+    ///
+    /// ```text
+    /// error: Variable used but not declared
+    ///  --> example.med:2:5
+    ///   |
+    /// 2 | x + y -> @;
+    ///   |     ^ not declared
+    ///   |
+    ///   = help: Declare the variable before using it, e.g. `int y;`
+    /// ```
+    pub fn render(&self, file_name: &str, source_text: &str) -> String {
+        let mut text = format!("error: {}\n", self.message);
+
+        // Everything below the header is indented to line up with the widest line number
+        let mut gutter = String::new();
+
+        if let Some((start, end)) = self.range(source_text) {
+            let line_text = source_text.lines().nth(start.line - 1).unwrap_or("");
+            let line_number = start.line.to_string();
+            gutter = " ".repeat(line_number.len());
+
+            // Only the first line of a span that covers several lines is underlined
+            let end_column = if end.line == start.line {
+                end.column
+            } else {
+                line_text.chars().count() + 1
+            };
+            let underline = "^".repeat(end_column.saturating_sub(start.column).max(1));
+
+            text += &format!("{gutter}--> {file_name}:{}:{}\n", start.line, start.column);
+            text += &format!("{gutter} |\n");
+            text += &format!("{line_number} | {line_text}\n");
+            text += &format!("{gutter} | {}{underline}", " ".repeat(start.column - 1));
+
+            if let Some(label) = &self.label {
+                text += &format!(" {label}");
+            }
+
+            text += "\n";
+        }
+
+        if !self.notes.is_empty() || self.help.is_some() {
+            text += &format!("{gutter} |\n");
+        }
+
+        for note in self.notes.iter() {
+            text += &format!("{gutter} = note: {note}\n");
+        }
+
+        if let Some(help) = &self.help {
+            text += &format!("{gutter} = help: {help}\n");
+        }
+
+        text
+    }
+}
+
+/// A line and column in the source text, both starting at 1. Columns are counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Finds the line and column of a byte offset into the source text.
+    pub fn from_offset(source_text: &str, offset: usize) -> Position {
+        let before = &source_text[..offset.min(source_text.len())];
+
+        let line_start = match before.rfind('\n') {
+            Some(index) => index + 1,
+            None => 0,
+        };
+
+        Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+/// Every diagnostic produced by a failed compile, in the order they were found.
+#[derive(Debug, Clone)]
+pub struct CompileError {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl CompileError {
+    /// Formats every diagnostic for the terminal. See `Diagnostic::render`.
+    pub fn render(&self, file_name: &str, source_text: &str) -> String {
+        self.diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.render(file_name, source_text))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// The code that the compiler should exit with. A program that failed while it was being interpreted exits
+    /// with the same code that the compiled program would have, and anything else exits with 1.
+    pub fn exit_code(&self) -> u8 {
+        match self.diagnostics.first().map(|diagnostic| diagnostic.kind) {
+            Some(ErrorKind::Runtime(error)) => error.exit_code(),
+            _ => 1,
+        }
+    }
+}
+
+impl From<Diagnostic> for CompileError {
+    fn from(diagnostic: Diagnostic) -> CompileError {
+        CompileError {
+            diagnostics: vec![diagnostic],
+        }
+    }
+}
+
+impl std::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let messages: Vec<&str> = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.as_str())
+            .collect();
+
+        write!(f, "{}", messages.join("\n"))
+    }
+}
+
+impl std::error::Error for CompileError {}
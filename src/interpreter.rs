@@ -0,0 +1,799 @@
+// A tree-walking interpreter for Medusa programs. It runs over the typed AST produced by the checker and
+// follows the same rules as the generated assembly (including the quirks of the conversion routines in
+// src/assembly/), so programs can be run instantly without an assembler or linker.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use crate::ast::*;
+use crate::{check_from_text, CompileError, Diagnostic, ErrorKind, RuntimeError};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    /// Strings are kept as bytes, just like in the compiled program, since they don't have to be valid UTF-8.
+    String(Vec<u8>),
+    Bool(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// The value of a variable that has been declared, but never assigned to.
+    fn default_for(datatype: VariableDataType) -> Value {
+        match datatype {
+            VariableDataType::INT => Value::Int(0),
+            VariableDataType::FLOAT => Value::Float(0.0),
+            VariableDataType::STRING => Value::String(Vec::new()),
+            VariableDataType::BOOL => Value::Bool(false),
+        }
+    }
+}
+
+struct InterpreterContext<'a> {
+    input: &'a mut dyn BufRead,
+    output: &'a mut dyn Write,
+    /// The variables that can be seen from the code being run. Each function call gets its own set.
+    variables: HashMap<String, Value>,
+    functions: HashMap<&'a str, &'a Function>,
+}
+
+fn error(kind: ErrorKind, span: Option<Span>, message: &str) -> CompileError {
+    let diagnostic = Diagnostic::new(kind, message);
+
+    match span {
+        Some(span) => diagnostic.with_span(span).into(),
+        None => diagnostic.into(),
+    }
+}
+
+fn runtime_error(error: RuntimeError, span: Span) -> CompileError {
+    Diagnostic::new(ErrorKind::Runtime(error), error.message())
+        .with_span(span)
+        .into()
+}
+
+/// Runs the source text, reading from `input` whenever the program reads from the IO portal and writing
+/// everything that the program outputs (including the header and footer) to `output`.
+pub fn interpret_from_text(
+    source_text: &str,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), CompileError> {
+    let program = check_from_text(source_text)?;
+
+    interpret_program(&program, input, output)
+}
+
+/// Runs a program that has already been checked. See `interpret_from_text`.
+pub fn interpret_program(
+    program: &Program,
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+) -> Result<(), CompileError> {
+    let mut context = InterpreterContext {
+        input,
+        output,
+        variables: HashMap::new(),
+        functions: program
+            .functions
+            .iter()
+            .map(|function| (function.name.as_str(), function))
+            .collect(),
+    };
+
+    let medusa_version = env!("CARGO_PKG_VERSION");
+
+    // The header and footer include the null terminator, just like the compiled program's
+    write_output(
+        &mut context,
+        format!("Medusa {medusa_version}\n\0").as_bytes(),
+    )?;
+
+    for statement in program.statements.iter() {
+        interpret_statement(statement, &mut context)?;
+    }
+
+    write_output(&mut context, b"Program ended\n\0")?;
+
+    context
+        .output
+        .flush()
+        .map_err(|e| error(ErrorKind::Io, None, &format!("Could not write output: {e}")))
+}
+
+fn write_output(context: &mut InterpreterContext, bytes: &[u8]) -> Result<(), CompileError> {
+    context
+        .output
+        .write_all(bytes)
+        .map_err(|e| error(ErrorKind::Io, None, &format!("Could not write output: {e}")))
+}
+
+/// Mirrors int_to_string.asm.
+fn int_to_string(value: i64) -> String {
+    value.to_string()
+}
+
+/// Mirrors float_to_string.asm: six digits past the decimal point, truncated rather than rounded. Anything
+/// too big for an int (including NaN and infinity) has a quotient and fraction of i64::MIN, which the
+/// assembly prints as if it were unsigned.
+fn float_to_string(value: f64) -> String {
+    let sign = if value.is_sign_negative() { "-" } else { "" };
+    let value = value.abs();
+
+    let quotient = truncate_float(value);
+    let fraction = truncate_float((value - quotient as f64) * 1000000.0);
+
+    // Only the last six digits of the fraction are printed
+    format!("{sign}{}.{:06}", quotient as u64, fraction as u64 % 1000000)
+}
+
+/// Mirrors string_to_int.asm: an optional sign followed by at least one digit, stopping at the first control
+/// character. Anything else in the string makes the conversion fail.
+fn string_to_int(bytes: &[u8]) -> Option<i64> {
+    let (negative, mut i) = match bytes.first() {
+        Some(b'+') => (false, 1),
+        Some(b'-') => (true, 1),
+        _ => (false, 0),
+    };
+
+    let mut result: i64 = 0;
+    let start = i;
+
+    while i < bytes.len() && bytes[i] >= 32 {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+
+        result = result
+            .wrapping_mul(10)
+            .wrapping_add((bytes[i] - b'0') as i64);
+        i += 1;
+    }
+
+    if i == start {
+        return None;
+    }
+
+    Some(if negative {
+        result.wrapping_neg()
+    } else {
+        result
+    })
+}
+
+/// Mirrors string_to_float.asm: an optional sign, the quotient, and an optional fraction after a decimal
+/// point, with at least one digit, stopping at the first null character. Anything else in the string makes
+/// the conversion fail.
+fn string_to_float(bytes: &[u8]) -> Option<f64> {
+    let bytes = match bytes.iter().position(|&x| x == 0) {
+        Some(end) => &bytes[..end],
+        None => bytes,
+    };
+
+    let (negative, mut i) = match bytes.first() {
+        Some(b'+') => (false, 1),
+        Some(b'-') => (true, 1),
+        _ => (false, 0),
+    };
+
+    let start = i;
+    let mut quotient: i64 = 0;
+    let mut fraction: i64 = 0;
+    let mut fraction_digits = 0;
+    let mut reading_fraction = false;
+
+    while i < bytes.len() {
+        let character = bytes[i];
+        i += 1;
+
+        if character == b'.' && !reading_fraction {
+            reading_fraction = true;
+            continue;
+        }
+
+        if !character.is_ascii_digit() {
+            return None;
+        }
+
+        let digit = (character - b'0') as i64;
+
+        if reading_fraction {
+            fraction = fraction.wrapping_mul(10).wrapping_add(digit);
+            fraction_digits += 1;
+        } else {
+            quotient = quotient.wrapping_mul(10).wrapping_add(digit);
+        }
+    }
+
+    // The fraction is divided one digit at a time, exactly like the assembly does, so that both produce
+    // the same bits
+    let mut value = fraction as f64;
+    for _ in 0..fraction_digits {
+        value /= 10.0;
+    }
+    value += quotient as f64;
+
+    if !bytes[start..].iter().any(u8::is_ascii_digit) {
+        return None;
+    }
+
+    Some(if negative { -value } else { value })
+}
+
+/// Mirrors cvtsd2si, which rounds to the nearest integer (ties to even) and returns the smallest integer for
+/// anything that doesn't fit.
+fn float_to_int(value: f64) -> i64 {
+    let rounded = value.round_ties_even();
+
+    if (-9223372036854775808.0..9223372036854775808.0).contains(&rounded) {
+        rounded as i64
+    } else {
+        i64::MIN
+    }
+}
+
+/// Mirrors cvttsd2si, which rounds towards zero and returns the smallest integer for anything that doesn't
+/// fit.
+fn truncate_float(value: f64) -> i64 {
+    if (-9223372036854775808.0..9223372036854775808.0).contains(&value) {
+        value as i64
+    } else {
+        i64::MIN
+    }
+}
+
+/// Mirrors compute_integer_power.asm: exponentiation by squaring, with negative powers returning 0.
+fn integer_power(mut x: i64, mut y: i64) -> i64 {
+    if y < 0 {
+        return 0;
+    }
+
+    let mut result: i64 = 1;
+
+    loop {
+        if y & 1 == 1 {
+            result = result.wrapping_mul(x);
+        }
+
+        y >>= 1;
+        if y == 0 {
+            break;
+        }
+
+        x = x.wrapping_mul(x);
+    }
+
+    result
+}
+
+/// Mirrors compute_float_power.asm: integer powers use exponentiation by squaring so that they're exact, and
+/// everything else follows C's pow().
+fn float_power(x: f64, y: f64) -> f64 {
+    if y == 0.0 || x == 1.0 {
+        return 1.0;
+    }
+
+    if x.is_nan() || y.is_nan() {
+        return f64::NAN;
+    }
+
+    let exponent = truncate_float(y);
+
+    if exponent != i64::MIN && exponent as f64 == y {
+        let mut base = x;
+        let mut remaining = exponent.unsigned_abs();
+        let mut result = 1.0;
+
+        loop {
+            if remaining & 1 == 1 {
+                result *= base;
+            }
+
+            remaining >>= 1;
+            if remaining == 0 {
+                break;
+            }
+
+            base *= base;
+        }
+
+        return if exponent < 0 { 1.0 / result } else { result };
+    }
+
+    if exponent != i64::MIN && x < 0.0 {
+        // A negative number can't be raised to a fractional power
+        return f64::NAN;
+    }
+
+    x.abs().powf(y)
+}
+
+fn value_to_string(value: Value) -> Vec<u8> {
+    match value {
+        Value::Int(x) => int_to_string(x).into_bytes(),
+        Value::Float(x) => float_to_string(x).into_bytes(),
+        Value::String(x) => x,
+        Value::Bool(x) => x.to_string().into_bytes(),
+        Value::Array(_) => unreachable!("the checker doesn't allow arrays to be used as values"),
+    }
+}
+
+fn interpret_expression(
+    expression: &Expression,
+    context: &mut InterpreterContext,
+) -> Result<Value, CompileError> {
+    match &expression.kind {
+        ExpressionKind::Int(value) => Ok(Value::Int(*value)),
+        ExpressionKind::Float(value) => Ok(Value::Float(*value)),
+        ExpressionKind::String(value) => Ok(Value::String(value.clone())),
+        ExpressionKind::Bool(value) => Ok(Value::Bool(*value)),
+        ExpressionKind::Variable(name) => Ok(context
+            .variables
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| Value::default_for(expression.datatype))),
+        ExpressionKind::Element { name, index } => {
+            let index = interpret_index(name, index, context)?;
+
+            match context.variables.get(name) {
+                Some(Value::Array(elements)) => Ok(elements[index].clone()),
+                _ => unreachable!("interpret_index only succeeds for arrays"),
+            }
+        }
+        ExpressionKind::Length(name) => match context.variables.get(name) {
+            Some(Value::Array(elements)) => Ok(Value::Int(elements.len() as i64)),
+            _ => Ok(Value::Int(0)),
+        },
+        ExpressionKind::Binary {
+            operator,
+            left,
+            right,
+        } => {
+            let left_operand = interpret_expression(left, context)?;
+            let right_operand = interpret_expression(right, context)?;
+
+            // The checker has already made sure that both operands have the right datatypes
+            Ok(match (left_operand, right_operand) {
+                (Value::Int(x), Value::Int(y)) => Value::Int(match operator {
+                    BinaryOperator::Add => x.wrapping_add(y),
+                    BinaryOperator::Subtract => x.wrapping_sub(y),
+                    BinaryOperator::Multiply => x.wrapping_mul(y),
+                    BinaryOperator::Divide => {
+                        if y == 0 {
+                            return Err(runtime_error(
+                                RuntimeError::DivisionByZero,
+                                expression.span,
+                            ));
+                        }
+                        x.wrapping_div(y)
+                    }
+                    BinaryOperator::Modulo => {
+                        if y == 0 {
+                            return Err(runtime_error(
+                                RuntimeError::DivisionByZero,
+                                expression.span,
+                            ));
+                        }
+                        x.wrapping_rem(y)
+                    }
+                    BinaryOperator::Power => integer_power(x, y),
+                    BinaryOperator::Concatenate => unreachable!(),
+                }),
+                (Value::Float(x), Value::Float(y)) => Value::Float(match operator {
+                    BinaryOperator::Add => x + y,
+                    BinaryOperator::Subtract => x - y,
+                    BinaryOperator::Multiply => x * y,
+                    BinaryOperator::Divide => x / y,
+                    BinaryOperator::Power => float_power(x, y),
+                    BinaryOperator::Modulo | BinaryOperator::Concatenate => unreachable!(),
+                }),
+                (Value::String(x), Value::String(y)) => Value::String([x, y].concat()),
+                _ => unreachable!(),
+            })
+        }
+        ExpressionKind::Comparison {
+            operator,
+            left,
+            right,
+        } => {
+            let left_operand = interpret_expression(left, context)?;
+            let right_operand = interpret_expression(right, context)?;
+
+            Ok(Value::Bool(compare(*operator, left_operand, right_operand)))
+        }
+        ExpressionKind::Logical {
+            operator,
+            left,
+            right,
+        } => {
+            // The right operand is only evaluated when the left one doesn't decide the result, just like the
+            // jumps in the compiled program
+            let left_operand = interpret_condition(left, context)?;
+
+            Ok(Value::Bool(match operator {
+                LogicalOperator::And => left_operand && interpret_condition(right, context)?,
+                LogicalOperator::Or => left_operand || interpret_condition(right, context)?,
+            }))
+        }
+        ExpressionKind::Not(operand) => Ok(Value::Bool(!interpret_condition(operand, context)?)),
+        ExpressionKind::Negate(operand) => Ok(match interpret_expression(operand, context)? {
+            Value::Int(x) => Value::Int(x.wrapping_neg()),
+            Value::Float(x) => Value::Float(-x),
+            _ => unreachable!("the checker only allows numbers to be negated"),
+        }),
+        ExpressionKind::Call { name, arguments } => interpret_call(name, arguments, context),
+        ExpressionKind::Cast(operand) => {
+            let value = interpret_expression(operand, context)?;
+
+            Ok(match (expression.datatype, value) {
+                (VariableDataType::INT, Value::Float(x)) => Value::Int(float_to_int(x)),
+                // Casting a string that isn't a number gives 0, rather than failing like input does
+                (VariableDataType::INT, Value::String(x)) => {
+                    Value::Int(string_to_int(&x).unwrap_or(0))
+                }
+                (VariableDataType::FLOAT, Value::Int(x)) => Value::Float(x as f64),
+                (VariableDataType::FLOAT, Value::String(x)) => {
+                    Value::Float(string_to_float(&x).unwrap_or(0.0))
+                }
+                (VariableDataType::STRING, value) => Value::String(value_to_string(value)),
+                // This is useless code - no conversion necessary
+                (_, value) => value,
+            })
+        }
+    }
+}
+
+fn interpret_declaration(
+    declaration: &Declaration,
+    context: &mut InterpreterContext,
+) -> Result<Completion, CompileError> {
+    // Every time a declaration runs (e.g. on each iteration of a loop), the variable starts over
+    context.variables.insert(
+        declaration.name.clone(),
+        Value::default_for(declaration.datatype),
+    );
+
+    match &declaration.value {
+        DeclarationValue::None => Ok(Completion::Normal),
+        DeclarationValue::Assignment(assignment) => {
+            interpret_assignment(assignment, context)?;
+            Ok(Completion::Normal)
+        }
+        DeclarationValue::Input(input) => interpret_input(input, context),
+    }
+}
+
+fn interpret_array_declaration(
+    declaration: &ArrayDeclaration,
+    context: &mut InterpreterContext,
+) -> Result<(), CompileError> {
+    let length = match interpret_expression(&declaration.length, context)? {
+        Value::Int(length) => length,
+        _ => unreachable!("the checker only allows int array lengths"),
+    };
+
+    if length < 0 {
+        return Err(runtime_error(
+            RuntimeError::NegativeArrayLength,
+            declaration.length.span,
+        ));
+    }
+
+    let elements = vec![Value::default_for(declaration.element_datatype); length as usize];
+    context
+        .variables
+        .insert(declaration.name.clone(), Value::Array(elements));
+
+    Ok(())
+}
+
+/// Evaluates the index of an element, making sure that it's inside the array.
+fn interpret_index(
+    name: &str,
+    index: &Expression,
+    context: &mut InterpreterContext,
+) -> Result<usize, CompileError> {
+    let index_value = interpret_expression(index, context)?;
+
+    check_index(name, index_value, index.span, context)
+}
+
+/// Makes sure that an index that has already been evaluated is inside the array.
+fn check_index(
+    name: &str,
+    index_value: Value,
+    span: Span,
+    context: &InterpreterContext,
+) -> Result<usize, CompileError> {
+    let length = match context.variables.get(name) {
+        Some(Value::Array(elements)) => elements.len(),
+        _ => 0,
+    };
+
+    match index_value {
+        Value::Int(index_value) if index_value >= 0 && (index_value as usize) < length => {
+            Ok(index_value as usize)
+        }
+        _ => Err(runtime_error(RuntimeError::IndexOutOfBounds, span)),
+    }
+}
+
+fn interpret_element_assignment(
+    assignment: &ElementAssignment,
+    context: &mut InterpreterContext,
+) -> Result<(), CompileError> {
+    // The index is worked out before the value, but it isn't checked until the value has been worked out as
+    // well, just like in the compiled program
+    let index_value = interpret_expression(&assignment.index, context)?;
+    let value = interpret_expression(&assignment.value, context)?;
+    let index = check_index(
+        &assignment.name,
+        index_value,
+        assignment.index.span,
+        context,
+    )?;
+
+    if let Some(Value::Array(elements)) = context.variables.get_mut(&assignment.name) {
+        elements[index] = value;
+    }
+
+    Ok(())
+}
+
+fn interpret_assignment(
+    assignment: &Assignment,
+    context: &mut InterpreterContext,
+) -> Result<(), CompileError> {
+    let value = interpret_expression(&assignment.value, context)?;
+
+    context.variables.insert(assignment.name.clone(), value);
+
+    Ok(())
+}
+
+fn interpret_output(output: &Output, context: &mut InterpreterContext) -> Result<(), CompileError> {
+    let text = value_to_string(interpret_expression(&output.value, context)?);
+
+    write_output(context, &text)?;
+    write_output(context, b"\n")
+}
+
+fn interpret_input(
+    input: &Input,
+    context: &mut InterpreterContext,
+) -> Result<Completion, CompileError> {
+    // Read one line, then cut it off at the first carriage return or newline
+    let mut line = Vec::new();
+    context
+        .input
+        .read_until(b'\n', &mut line)
+        .map_err(|e| error(ErrorKind::Io, None, &format!("Could not read input: {e}")))?;
+
+    if let Some(end) = line.iter().position(|&x| x == b'\r' || x == b'\n') {
+        line.truncate(end);
+    }
+
+    let value = match input.datatype {
+        VariableDataType::INT => string_to_int(&line).map(Value::Int),
+        VariableDataType::FLOAT => string_to_float(&line).map(Value::Float),
+        VariableDataType::STRING => Some(Value::String(line)),
+        // The checker doesn't allow bools to be read from input
+        VariableDataType::BOOL => unreachable!(),
+    };
+
+    let valid = value.is_some();
+
+    // Just like in the compiled program, a number that couldn't be read is stored as 0
+    context.variables.insert(
+        input.name.clone(),
+        value.unwrap_or_else(|| Value::default_for(input.datatype)),
+    );
+
+    if valid {
+        return Ok(Completion::Normal);
+    }
+
+    match &input.else_body {
+        Some(else_body) => interpret_block(else_body, context),
+        None => Err(runtime_error(
+            RuntimeError::InvalidInput(input.datatype),
+            input.span,
+        )),
+    }
+}
+
+fn interpret_condition(
+    condition: &Expression,
+    context: &mut InterpreterContext,
+) -> Result<bool, CompileError> {
+    match interpret_expression(condition, context)? {
+        Value::Bool(x) => Ok(x),
+        _ => unreachable!(),
+    }
+}
+
+fn compare(operator: ConditionalOperator, left: Value, right: Value) -> bool {
+    match (left, right) {
+        (Value::Int(x), Value::Int(y)) => match operator {
+            ConditionalOperator::Greater => x > y,
+            ConditionalOperator::Less => x < y,
+            ConditionalOperator::GreaterOrEqual => x >= y,
+            ConditionalOperator::LessOrEqual => x <= y,
+            ConditionalOperator::Equal => x == y,
+            ConditionalOperator::NotEqual => x != y,
+        },
+        // Rust's float comparisons follow IEEE 754, just like ucomisd - NaN is unordered, so every
+        // comparison with it is false except for !=
+        (Value::Float(x), Value::Float(y)) => match operator {
+            ConditionalOperator::Greater => x > y,
+            ConditionalOperator::Less => x < y,
+            ConditionalOperator::GreaterOrEqual => x >= y,
+            ConditionalOperator::LessOrEqual => x <= y,
+            ConditionalOperator::Equal => x == y,
+            ConditionalOperator::NotEqual => x != y,
+        },
+        // Strings are compared byte by byte, exactly like medusa_compare_strings
+        (Value::String(x), Value::String(y)) => match operator {
+            ConditionalOperator::Greater => x > y,
+            ConditionalOperator::Less => x < y,
+            ConditionalOperator::GreaterOrEqual => x >= y,
+            ConditionalOperator::LessOrEqual => x <= y,
+            ConditionalOperator::Equal => x == y,
+            ConditionalOperator::NotEqual => x != y,
+        },
+        // The checker only allows bools to be compared with == and !=
+        (Value::Bool(x), Value::Bool(y)) => match operator {
+            ConditionalOperator::Equal => x == y,
+            ConditionalOperator::NotEqual => x != y,
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// How a statement finished running.
+enum Completion {
+    /// Carry on with the next statement.
+    Normal,
+    /// A return statement ran, so the rest of the function is skipped.
+    Return(Value),
+    /// A break statement ran, so the rest of the innermost loop is skipped.
+    Break,
+    /// A continue statement ran, so the rest of the innermost loop's body is skipped.
+    Continue,
+}
+
+/// Runs the statements in order, stopping early if one of them returns, breaks, or continues.
+fn interpret_block(
+    statements: &[Statement],
+    context: &mut InterpreterContext,
+) -> Result<Completion, CompileError> {
+    for statement in statements.iter() {
+        match interpret_statement(statement, context)? {
+            Completion::Normal => {}
+            completion => return Ok(completion),
+        }
+    }
+
+    Ok(Completion::Normal)
+}
+
+fn interpret_call(
+    name: &str,
+    arguments: &[Expression],
+    context: &mut InterpreterContext,
+) -> Result<Value, CompileError> {
+    let function = context.functions[name];
+
+    // Every call gets its own variables, starting with the parameters
+    let mut variables = HashMap::new();
+
+    for (parameter, argument) in function.parameters.iter().zip(arguments.iter()) {
+        variables.insert(
+            parameter.name.clone(),
+            interpret_expression(argument, context)?,
+        );
+    }
+
+    let caller_variables = std::mem::replace(&mut context.variables, variables);
+    let completion = interpret_block(&function.body, context);
+    context.variables = caller_variables;
+
+    match completion? {
+        Completion::Return(value) => Ok(value),
+        // The checker makes sure that every function returns a value, and that break and continue are only
+        // used inside of loops
+        _ => unreachable!(),
+    }
+}
+
+fn interpret_if(
+    if_statement: &If,
+    context: &mut InterpreterContext,
+) -> Result<Completion, CompileError> {
+    if interpret_condition(&if_statement.condition, context)? {
+        return interpret_block(&if_statement.body, context);
+    }
+
+    for else_if in if_statement.else_ifs.iter() {
+        if interpret_condition(&else_if.condition, context)? {
+            return interpret_block(&else_if.body, context);
+        }
+    }
+
+    match &if_statement.else_body {
+        Some(else_body) => interpret_block(else_body, context),
+        None => Ok(Completion::Normal),
+    }
+}
+
+fn interpret_forloop(
+    forloop: &ForLoop,
+    context: &mut InterpreterContext,
+) -> Result<Completion, CompileError> {
+    // The initializer isn't part of the loop, so a break in it (from an input's else block) belongs to a
+    // loop further out
+    if let Some(initializer) = &forloop.initializer {
+        match interpret_statement(initializer, context)? {
+            Completion::Normal => {}
+            completion => return Ok(completion),
+        }
+    }
+
+    while interpret_condition(&forloop.condition, context)? {
+        match interpret_block(&forloop.body, context)? {
+            Completion::Return(value) => return Ok(Completion::Return(value)),
+            Completion::Break => break,
+            Completion::Normal | Completion::Continue => {}
+        }
+
+        interpret_assignment(&forloop.step, context)?;
+    }
+
+    Ok(Completion::Normal)
+}
+
+fn interpret_while(
+    while_loop: &While,
+    context: &mut InterpreterContext,
+) -> Result<Completion, CompileError> {
+    while interpret_condition(&while_loop.condition, context)? {
+        match interpret_block(&while_loop.body, context)? {
+            Completion::Return(value) => return Ok(Completion::Return(value)),
+            Completion::Break => break,
+            Completion::Normal | Completion::Continue => {}
+        }
+    }
+
+    Ok(Completion::Normal)
+}
+
+fn interpret_statement(
+    statement: &Statement,
+    context: &mut InterpreterContext,
+) -> Result<Completion, CompileError> {
+    match statement {
+        Statement::Declaration(declaration) => return interpret_declaration(declaration, context),
+        Statement::ArrayDeclaration(declaration) => {
+            interpret_array_declaration(declaration, context)?
+        }
+        Statement::Assignment(assignment) => interpret_assignment(assignment, context)?,
+        Statement::ElementAssignment(assignment) => {
+            interpret_element_assignment(assignment, context)?
+        }
+        Statement::Output(output) => interpret_output(output, context)?,
+        Statement::Input(input) => return interpret_input(input, context),
+        Statement::If(if_statement) => return interpret_if(if_statement, context),
+        Statement::ForLoop(forloop) => return interpret_forloop(forloop, context),
+        Statement::While(while_loop) => return interpret_while(while_loop, context),
+        Statement::Return(return_statement) => {
+            let value = interpret_expression(&return_statement.value, context)?;
+            return Ok(Completion::Return(value));
+        }
+        Statement::Break(_) => return Ok(Completion::Break),
+        Statement::Continue(_) => return Ok(Completion::Continue),
+    }
+
+    Ok(Completion::Normal)
+}
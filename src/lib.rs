@@ -3,14 +3,25 @@
 
 use std::collections::HashMap;
 use std::fs;
-use std::process::Command;
 
-use pest::iterators::Pair;
-use pest::Parser;
 use pest_derive::Parser;
 use rust_embed::Embed;
-use winreg::enums::HKEY_LOCAL_MACHINE;
-use winreg::RegKey;
+
+pub mod ast;
+mod checker;
+mod error;
+mod interpreter;
+mod toolchain;
+
+use ast::{
+    ArrayDeclaration, Assignment, BinaryOperator, ConditionalOperator, Declaration,
+    DeclarationValue, ElementAssignment, Expression, ExpressionKind, ForLoop, Function, If, Input,
+    LogicalOperator, Output, Return, Span, Statement, While,
+};
+pub use checker::check_from_text;
+pub use error::{CompileError, Diagnostic, ErrorKind, Position, RuntimeError};
+pub use interpreter::{interpret_from_text, interpret_program};
+pub use toolchain::Toolchain;
 
 #[derive(Parser)]
 #[grammar = "medusa.pest"]
@@ -20,37 +31,134 @@ pub struct MedusaParser;
 #[folder = "src/assembly/"]
 struct Assembly;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum VariableDataType {
+/// The platform that a Medusa program is compiled for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// Win64 executables, linked against kernel32 with ld.lld.
+    Windows,
+    /// Linux x86-64 ELF executables that talk to the kernel through system calls.
+    Linux,
+}
+
+impl Target {
+    /// Returns the target that matches the platform the compiler is running on.
+    pub fn host() -> Target {
+        if cfg!(windows) {
+            Target::Windows
+        } else {
+            Target::Linux
+        }
+    }
+
+    /// Returns the file name of the object file produced by the assembler for this target.
+    pub fn object_file_name(&self, output_file_name: &str) -> String {
+        match self {
+            Target::Windows => format!("{output_file_name}.obj"),
+            Target::Linux => format!("{output_file_name}.o"),
+        }
+    }
+
+    /// Returns the file name of the executable produced by the linker for this target.
+    pub fn executable_file_name(&self, output_file_name: &str) -> String {
+        match self {
+            Target::Windows => format!("{output_file_name}.exe"),
+            Target::Linux => output_file_name.to_string(),
+        }
+    }
+
+    /// The folder inside of src/assembly/ that holds the startup code and runtime routines for this target.
+    fn assembly_folder(&self) -> &'static str {
+        match self {
+            Target::Windows => "windows",
+            Target::Linux => "linux",
+        }
+    }
+}
+
+impl std::str::FromStr for Target {
+    type Err = CompileError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "windows" => Ok(Target::Windows),
+            "linux" => Ok(Target::Linux),
+            _ => Err(CompileError::from(Diagnostic::new(
+                ErrorKind::Usage,
+                format!("Unknown target '{s}', expected 'windows' or 'linux'"),
+            ))),
+        }
+    }
+}
+
+/// The datatype of a variable or expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum VariableDataType {
     INT,
     STRING,
     FLOAT,
+    BOOL,
+}
+
+impl std::fmt::Display for VariableDataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            VariableDataType::INT => write!(f, "int"),
+            VariableDataType::STRING => write!(f, "string"),
+            VariableDataType::FLOAT => write!(f, "float"),
+            VariableDataType::BOOL => write!(f, "bool"),
+        }
+    }
 }
 
 struct CompilerContext {
+    /// Used to find the line that a runtime error happened on.
+    source_text: String,
     variable_index: u64,
     label_index: u64,
     assembly_text: String,
     assembly_data: String,
-    variables: HashMap<String, VariableDataType>,
+    /// The stack frame offset (below RBP) of every variable in the function being compiled. Empty outside of
+    /// functions, where every variable is global.
+    local_variables: HashMap<String, u64>,
+    /// The string variables of the function being compiled, which are freed whenever the function returns.
+    local_strings: Vec<String>,
+    /// The arrays of the function being compiled (along with their element datatypes), which are also freed
+    /// whenever the function returns.
+    local_arrays: Vec<(String, VariableDataType)>,
+    /// The labels that `continue` and `break` jump to for every loop around the code being compiled,
+    /// innermost last.
+    loop_labels: Vec<LoopLabels>,
+    overflow_checks: bool,
 }
 
-#[derive(Debug, Clone)]
-pub struct CompileError {
-    body: String,
+#[derive(Debug, Clone, Copy)]
+struct LoopLabels {
+    continue_label: u64,
+    break_label: u64,
 }
 
-impl std::fmt::Display for CompileError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.body)
-    }
+/// Settings that change the code the compiler generates, without changing what the language accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CompileOptions {
+    /// Stop the program with a runtime error when integer arithmetic overflows, instead of letting the
+    /// result wrap around. Off by default, since every add, subtract, multiply and power has to be checked.
+    pub overflow_checks: bool,
 }
 
-fn print_assembly_with_context(file_name: &str, context: &mut CompilerContext) {
+/// Assembly files in src/assembly/ that are emitted once at the end of every program, rather than being
+/// pasted in wherever they're used.
+const RUNTIME_ROUTINES: [&str; 3] = ["arrays", "compare_strings", "strings"];
+
+fn load_assembly(file_name: &str) -> String {
     // We're using Rust embed to store the assembly files directly in the .exe - these files
     // can be located in src/assembly/ before compilation.
     let data = Assembly::get(&format!("{file_name}.asm")).unwrap().data;
-    let source_text = std::str::from_utf8(data.as_ref()).unwrap();
+    std::str::from_utf8(data.as_ref()).unwrap().to_string()
+}
+
+fn print_assembly_with_context(file_name: &str, context: &mut CompilerContext) {
+    let source_text = load_assembly(file_name);
 
     let label_matcher = regex::Regex::new(r"[^\{]\{(?<label>[^\{\}]+)\}[^\}]").unwrap();
 
@@ -69,7 +177,7 @@ fn print_assembly_with_context(file_name: &str, context: &mut CompilerContext) {
         // Return the index of the label while preserving the first and last character of the match
         // (which are not actually a part of the label, it's confusing, I know)
         let raw_capture = x.get(0).unwrap().as_str();
-        raw_capture.chars().nth(0).unwrap().to_string()
+        raw_capture.chars().next().unwrap().to_string()
             + labels_to_indices
                 .get(x.name("label").unwrap().as_str())
                 .unwrap()
@@ -88,17 +196,88 @@ fn print_assembly_with_context(file_name: &str, context: &mut CompilerContext) {
     context.assembly_text += &processed_text;
 }
 
+/// Emits a check that stops the program with a runtime error, printing the error along with the line of
+/// source code that caused it. The check must come right after a comparison, and `jump_if_ok` is the jump
+/// instruction that skips over the error when everything is fine.
+fn medusa_runtime_error_check(
+    error: RuntimeError,
+    span: Span,
+    jump_if_ok: &str,
+    context: &mut CompilerContext,
+) {
+    let line = Position::from_offset(&context.source_text, span.start).line;
+    let line_text = context
+        .source_text
+        .lines()
+        .nth(line - 1)
+        .unwrap_or("")
+        .trim();
+
+    let message = format!(
+        "Runtime error on line {line}: {}\n{line} | {line_text}\n",
+        error.message()
+    );
+    let message_length = message.len();
+    let exit_code = error.exit_code();
+
+    let string_index = context.variable_index;
+    context.variable_index += 1;
+
+    let ok_label = context.label_index;
+    context.label_index += 1;
+
+    // The line of source code could contain anything (including quotes), so the message is written out
+    // byte by byte
+    context.assembly_data += &format!(
+        "string_{string_index} db {}\n",
+        nasm_byte_list(message.as_bytes())
+    );
+    context.assembly_text += &format!(
+        "{jump_if_ok} label_{ok_label}
+lea rdx, [rel string_{string_index}]
+mov r8, {message_length}
+call medusa_write
+mov rcx, {exit_code}
+call medusa_exit
+label_{ok_label}:
+"
+    );
+}
+
+/// Stops the program if the integer operation that was just emitted overflowed, as long as overflow checks
+/// are turned on. This has to come straight after the operation, before anything else touches the flags.
+fn medusa_overflow_check(span: Span, context: &mut CompilerContext) {
+    if context.overflow_checks {
+        medusa_runtime_error_check(RuntimeError::IntegerOverflow, span, "jno", context);
+    }
+}
+
+/// Writes out the bytes as a comma-separated list for a `db` directive. Text from the program is always
+/// emitted like this, since a quoted string in NASM can't hold every character.
+fn nasm_byte_list(bytes: &[u8]) -> String {
+    let bytes: Vec<String> = bytes.iter().map(|byte| byte.to_string()).collect();
+    bytes.join(", ")
+}
+
+/// Pops an index off the stack and loads the array into RAX and the index into RBX, stopping the program if
+/// the index is out of bounds. Arrays are stored on the heap as their length followed by their elements, so
+/// element i lives at [rax + rbx * 8 + 8].
+fn medusa_index_array(name: &str, span: Span, context: &mut CompilerContext) {
+    let memory = medusa_variable_memory(name, context);
+
+    // Comparing the index as an unsigned number catches negative indices too, since they look huge
+    context.assembly_text += &format!("pop rbx\nmov rax, {memory}\ncmp rbx, [rax]\n");
+    medusa_runtime_error_check(RuntimeError::IndexOutOfBounds, span, "jb", context);
+}
+
 /// Pops a float off the stack, converts it to an int, and pushes it back onto the stack.
 fn medusa_float_to_int(context: &mut CompilerContext) {
-    context.assembly_text += format!(
-        "
+    context.assembly_text += "
 pop rax
 movq xmm1, rax
 cvtsd2si rax, xmm1
 push rax
-"
-    )
-    .as_str();
+";
 }
 
 /// Pops an int off the stack, converts it to a float, and pushes it back onto the stack.
@@ -126,236 +305,226 @@ fn medusa_string_to_int(context: &mut CompilerContext) {
     print_assembly_with_context("string_to_int", context);
 }
 
-fn medusa_parse_expression(
-    pair: pest::iterators::Pair<Rule>,
+/// Pops a string off the stack, converts it to an int or a float, and pushes the result back onto the stack.
+/// The string is freed afterwards if it's a temporary.
+fn medusa_convert_string(
+    to_datatype: VariableDataType,
+    temporary: bool,
     context: &mut CompilerContext,
-) -> VariableDataType {
-    // Convert the entire expression to postfix notation and then convert it to assembly
-    // https://www.andrew.cmu.edu/course/15-200/s06/applications/ln/junk.html
-    let pairs = pair.into_inner();
-
-    let stack_precedence_map = HashMap::from([
-        (Rule::EOI, 0),
-        (Rule::add, 2),
-        (Rule::subtract, 2),
-        (Rule::multiply, 4),
-        (Rule::divide, 4),
-        (Rule::modulo, 4),
-        (Rule::concatenate, 6),
-        (Rule::power, 6),
-        (Rule::cast, 8),
-        (Rule::expression, 51),
-    ]);
-
-    let input_precedence_map = HashMap::from([
-        (Rule::add, 1),
-        (Rule::subtract, 1),
-        (Rule::multiply, 3),
-        (Rule::divide, 3),
-        (Rule::modulo, 3),
-        (Rule::concatenate, 5),
-        (Rule::power, 5),
-        (Rule::cast, 7),
-        (Rule::expression, 50),
-    ]);
-
-    let mut output = Vec::new();
-    let mut stack: Vec<Pair<Rule>> = Vec::new();
-
-    for pair in pairs {
-        match pair.as_rule() {
-            Rule::int | Rule::float | Rule::string | Rule::identifier => {
-                output.push(pair);
-            }
-            Rule::add
-            | Rule::subtract
-            | Rule::multiply
-            | Rule::divide
-            | Rule::modulo
-            | Rule::concatenate
-            | Rule::power
-            | Rule::cast
-            | Rule::expression => {
-                loop {
-                    let stack_precedence: i32 = match stack.last() {
-                        Some(x) => *stack_precedence_map.get(&x.as_rule()).unwrap(),
-                        None => 0,
-                    };
-
-                    let input_precedence = *input_precedence_map.get(&pair.as_rule()).unwrap();
-
-                    if stack_precedence >= input_precedence {
-                        if stack.last().is_none() {
-                            // No clue what this case is
-                            panic!("Unexpected case");
-                        } else {
-                            output.push(stack.pop().unwrap());
-                        }
-                    } else {
-                        stack.push(pair);
-                        break;
-                    }
-                }
-            }
-            _ => unreachable!(),
-        };
+) {
+    // The conversion routines use up the string, so keep a copy of the pointer underneath it
+    if temporary {
+        context.assembly_text += "push qword [rsp]\n";
     }
 
-    // All left-over operators in the stack are pushed to the output
-    while let Some(pair) = stack.pop() {
-        output.push(pair);
+    match to_datatype {
+        VariableDataType::INT => medusa_string_to_int(context),
+        VariableDataType::FLOAT => medusa_string_to_float(context),
+        _ => unreachable!(),
     }
 
-    //println!("Output!! {:#?}", output);
-    //println!("Stack!! {:#?}", stack);
+    if temporary {
+        context.assembly_text += "pop rax\npop rdx\ncall medusa_free_string\npush rax\n";
+    }
+}
 
-    // Generate assembly code from the postfix notation
-    let mut stack = Vec::new();
+/// Returns whether the expression evaluates to a temporary string, i.e. a string that nothing else owns,
+/// which has to be freed by whatever uses it. Literals aren't temporaries, and neither are the strings that
+/// belong to variables and array elements. Functions always return a string of their own.
+fn is_temporary_string(expression: &Expression) -> bool {
+    if expression.datatype != VariableDataType::STRING {
+        return false;
+    }
 
-    for token in output {
-        //println!("{:?} - {:?}", token, stack);
+    match &expression.kind {
+        ExpressionKind::String(_)
+        | ExpressionKind::Variable(_)
+        | ExpressionKind::Element { .. } => false,
+        ExpressionKind::Cast(operand) => {
+            operand.datatype != VariableDataType::STRING || is_temporary_string(operand)
+        }
+        _ => true,
+    }
+}
 
-        // If the current token is a variable, load it on the stack
-        match token.as_rule() {
-            Rule::int => {
-                context.assembly_text +=
-                    format!("mov rax, {}\npush rax\n", token.as_span().as_str()).as_str();
-                stack.push(VariableDataType::INT);
+/// Pops a bool off the stack, converts it to a string, and pushes it back onto the stack.
+fn medusa_bool_to_string(context: &mut CompilerContext) {
+    print_assembly_with_context("bool_to_string", context);
+}
+
+fn medusa_parse_expression(expression: &Expression, context: &mut CompilerContext) {
+    match &expression.kind {
+        ExpressionKind::Int(value) => {
+            context.assembly_text += format!("mov rax, {value}\npush rax\n").as_str();
+        }
+        ExpressionKind::Float(value) => {
+            // The float is loaded by its exact bit pattern, so that NASM never has to parse it
+            context.assembly_text +=
+                format!("mov rax, {:#x} ; {value:?}\npush rax\n", value.to_bits()).as_str();
+        }
+        ExpressionKind::String(value) => {
+            // Strings are never changed once they've been built, so the literal can be used straight from the
+            // data section, laid out just like a string on the heap (see strings.asm)
+            let string_index = context.variable_index;
+            context.variable_index += 1;
+
+            let length = value.len();
+            let mut bytes = value.clone();
+            bytes.push(0);
+
+            context.assembly_data += &format!(
+                "dq {length}, {length}\nstring_{string_index} db {}\n",
+                nasm_byte_list(&bytes)
+            );
+            context.assembly_text +=
+                format!("lea rax, [rel string_{string_index}]\npush rax\n").as_str();
+        }
+        ExpressionKind::Bool(value) => {
+            context.assembly_text += format!("mov rax, {}\npush rax\n", *value as u8).as_str();
+        }
+        ExpressionKind::Variable(identifier) => {
+            // String variables hold a pointer to their string, so every variable is read the same way
+            let memory = medusa_variable_memory(identifier, context);
+            context.assembly_text += format!("mov rax, {memory}\npush rax\n").as_str();
+        }
+        ExpressionKind::Element { name, index } => {
+            medusa_parse_expression(index, context);
+            medusa_index_array(name, expression.span, context);
+
+            // String elements hold a pointer to the string, just like string variables do
+            context.assembly_text += "push qword [rax + rbx * 8 + 8]\n";
+        }
+        ExpressionKind::Length(name) => {
+            let memory = medusa_variable_memory(name, context);
+            context.assembly_text += &format!("mov rax, {memory}\npush qword [rax]\n");
+        }
+        ExpressionKind::Call { name, arguments } => {
+            // The arguments are pushed in order, so the last argument ends up on top of the stack
+            for argument in arguments.iter() {
+                medusa_parse_expression(argument, context);
             }
-            Rule::float => {
-                context.assembly_text += format!(
-                    "mov rax, __float64__({})\npush rax\n",
-                    token.as_span().as_str()
-                )
-                .as_str();
-                stack.push(VariableDataType::FLOAT);
+
+            context.assembly_text += &format!("call function_{name}\n");
+
+            // Throw away the arguments (freeing the temporary strings, which the function made its own copies
+            // of), then push the return value in their place
+            for argument in arguments.iter().rev() {
+                if is_temporary_string(argument) {
+                    context.assembly_text += "pop rdx\ncall medusa_free_string\n";
+                } else {
+                    context.assembly_text += "add rsp, 8\n";
+                }
             }
-            Rule::string => {
-                // Copy the string into a variable in the data section
-                let string_index = context.variable_index;
-                context.variable_index += 1;
-
-                let loop_index = context.label_index;
-                context.label_index += 1;
-
-                let break_index = context.label_index;
-                context.label_index += 1;
-
-                let raw_string = token.as_span().as_str();
-                context.assembly_data +=
-                    format!("string_{string_index} db {raw_string}, 0\n").as_str();
-
-                // TODO: Fix memory leak - HeapAlloc for the string is never free'd
-
-                context.assembly_text += format!(
-                    "
-; Allocate space for string {} on the heap
-mov rcx, [rel heap_handle]
-mov rdx, 12
-mov r8, 256
-sub rsp, 32
-call HeapAlloc
-add rsp, 32
-
-; Copy the string
-mov rcx, 0
-lea r8, [rel string_{string_index}]
 
-label_{loop_index}:
-mov rdx, [r8 + rcx]
-cmp rdx, 0
-jz label_{break_index}
+            context.assembly_text += "push rax\n";
+        }
+        ExpressionKind::Binary {
+            operator,
+            left,
+            right,
+        } => {
+            // Both operands are left on the stack, with the right operand on top
+            medusa_parse_expression(left, context);
+            medusa_parse_expression(right, context);
+
+            match operator {
+                BinaryOperator::Concatenate => medusa_parse_concatenation(left, right, context),
+                _ => {
+                    medusa_parse_binary_operator(*operator, left.datatype, expression.span, context)
+                }
+            }
+        }
+        ExpressionKind::Comparison { .. } | ExpressionKind::Logical { .. } => {
+            // Comparisons are compiled into jumps, which are then turned into a 1 or a 0
+            let false_label = context.label_index;
+            context.label_index += 1;
 
-mov [rax + rcx], rdx
-inc rcx
-jmp label_{loop_index}
+            let end_label = context.label_index;
+            context.label_index += 1;
 
-label_{break_index}:
-push rax
-",
-                    token.as_span().as_str()
-                )
-                .as_str();
-                stack.push(VariableDataType::STRING);
-                //todo!();
+            medusa_parse_condition(expression, context, false_label);
+
+            context.assembly_text += &format!(
+                "push 1\njmp label_{end_label}\nlabel_{false_label}:\npush 0\nlabel_{end_label}:\n"
+            );
+        }
+        ExpressionKind::Not(operand) => {
+            medusa_parse_expression(operand, context);
+
+            context.assembly_text += "pop rax\nxor rax, 1\npush rax\n";
+        }
+        ExpressionKind::Negate(operand) => {
+            medusa_parse_expression(operand, context);
+
+            if operand.datatype == VariableDataType::INT {
+                context.assembly_text += "pop rax\nneg rax\n";
+                medusa_overflow_check(expression.span, context);
+                context.assembly_text += "push rax\n";
+            } else {
+                // Floats keep their sign in the top bit
+                context.assembly_text += "pop rax\nbtc rax, 63\npush rax\n";
             }
-            Rule::expression => {
-                stack.push(medusa_parse_expression(token, context));
-                continue;
+        }
+        ExpressionKind::Cast(operand) => {
+            medusa_parse_expression(operand, context);
+
+            let from_datatype = operand.datatype;
+            let to_datatype = expression.datatype;
+
+            if from_datatype == to_datatype {
+                // This is useless code - no conversion necessary
+                return;
             }
-            Rule::identifier => match context.variables.get(token.as_span().as_str()) {
-                Some(variable) => {
-                    let identifier = token.as_span().as_str();
-                    match variable {
-                        VariableDataType::INT => {
-                            context.assembly_text +=
-                                format!("mov rax, [rel var_{identifier}]\npush rax\n").as_str();
-                            stack.push(VariableDataType::INT);
-                        }
-                        VariableDataType::FLOAT => {
-                            context.assembly_text +=
-                                format!("mov rax, [rel var_{identifier}]\npush rax\n").as_str();
-                            stack.push(VariableDataType::FLOAT);
-                        }
-                        VariableDataType::STRING => {
-                            context.assembly_text +=
-                                format!("lea rax, [rel var_{identifier}]\npush rax\n").as_str();
-                            stack.push(VariableDataType::STRING);
-                        }
+
+            match from_datatype {
+                VariableDataType::INT => match to_datatype {
+                    VariableDataType::FLOAT => {
+                        medusa_int_to_float(context);
                     }
+                    VariableDataType::STRING => {
+                        medusa_int_to_string(context);
+                    }
+                    _ => unreachable!(),
+                },
+                VariableDataType::FLOAT => match to_datatype {
+                    VariableDataType::INT => {
+                        medusa_float_to_int(context);
+                    }
+                    VariableDataType::STRING => {
+                        medusa_float_to_string(context);
+                    }
+                    _ => unreachable!(),
+                },
+                VariableDataType::STRING => {
+                    medusa_convert_string(to_datatype, is_temporary_string(operand), context);
                 }
-                None => panic!("Variable used but not declared"),
-            },
-            _ => {}
-        }
-
-        // If the current token is an operation, make sure the semantic rules are not being violated (i.e. correct datatypes on all variables)
-        match token.as_rule() {
-            // Mathematical binary operations
-            Rule::add
-            | Rule::subtract
-            | Rule::multiply
-            | Rule::divide
-            | Rule::modulo
-            | Rule::power => {
-                //println!("{:?}", stack);
-                let left_operand = stack[stack.len() - 2];
-                let right_operand = stack[stack.len() - 1];
-
-                if left_operand != right_operand {
-                    panic!("Datatypes don't match");
-                } else if (left_operand != VariableDataType::INT
-                    && left_operand != VariableDataType::FLOAT)
-                    || (right_operand != VariableDataType::INT
-                        && right_operand != VariableDataType::FLOAT)
-                {
-                    panic!("Math operation on non-number");
-                }
-            }
-            Rule::concatenate => {
-                let left_operand = stack[stack.len() - 2];
-                let right_operand = stack[stack.len() - 1];
-
-                if left_operand != VariableDataType::STRING
-                    || right_operand != VariableDataType::STRING
-                {
-                    panic!("Concatenation on non-string");
-                }
+                VariableDataType::BOOL => match to_datatype {
+                    VariableDataType::STRING => {
+                        medusa_bool_to_string(context);
+                    }
+                    _ => unreachable!(),
+                },
             }
-            _ => {}
         }
+    }
+}
 
-        // If the current token is an operation, execute it in assembly
-        match token.as_rule() {
-            Rule::add => {
-                // Pop the top two numbers off the stack
-                stack.pop();
-                let datatype = stack.pop().unwrap();
-
-                if datatype == VariableDataType::INT {
-                    context.assembly_text += "pop rbx\npop rax\nadd rax, rbx\npush rax\n";
-                } else if datatype == VariableDataType::FLOAT {
-                    context.assembly_text += "
+/// Pops the top two values off the stack, applies the operator to them, and pushes the result back onto the
+/// stack. The checker has already made sure that both operands have the same datatype.
+fn medusa_parse_binary_operator(
+    operator: BinaryOperator,
+    datatype: VariableDataType,
+    span: Span,
+    context: &mut CompilerContext,
+) {
+    match operator {
+        BinaryOperator::Add => {
+            if datatype == VariableDataType::INT {
+                context.assembly_text += "pop rbx\npop rax\nadd rax, rbx\n";
+                medusa_overflow_check(span, context);
+                context.assembly_text += "push rax\n";
+            } else if datatype == VariableDataType::FLOAT {
+                context.assembly_text += "
 pop rbx
 movq xmm2, rbx
 pop rax
@@ -364,19 +533,15 @@ addpd xmm1, xmm2
 movq rax, xmm1
 push rax
 ";
-                }
-
-                stack.push(datatype);
             }
-            Rule::subtract => {
-                // Pop the top two numbers off the stack
-                stack.pop();
-                let datatype = stack.pop().unwrap();
-
-                if datatype == VariableDataType::INT {
-                    context.assembly_text += "pop rbx\npop rax\nsub rax, rbx\npush rax\n";
-                } else if datatype == VariableDataType::FLOAT {
-                    context.assembly_text += "
+        }
+        BinaryOperator::Subtract => {
+            if datatype == VariableDataType::INT {
+                context.assembly_text += "pop rbx\npop rax\nsub rax, rbx\n";
+                medusa_overflow_check(span, context);
+                context.assembly_text += "push rax\n";
+            } else if datatype == VariableDataType::FLOAT {
+                context.assembly_text += "
 pop rbx
 movq xmm2, rbx
 pop rax
@@ -385,19 +550,15 @@ subpd xmm1, xmm2
 movq rax, xmm1
 push rax
 ";
-                }
-
-                stack.push(datatype);
             }
-            Rule::multiply => {
-                // Pop the top two numbers off the stack
-                stack.pop();
-                let datatype = stack.pop().unwrap();
-
-                if datatype == VariableDataType::INT {
-                    context.assembly_text += "pop rbx\npop rax\nxor rdx, rdx\nimul rbx\npush rax\n";
-                } else if datatype == VariableDataType::FLOAT {
-                    context.assembly_text += "
+        }
+        BinaryOperator::Multiply => {
+            if datatype == VariableDataType::INT {
+                context.assembly_text += "pop rbx\npop rax\nxor rdx, rdx\nimul rbx\n";
+                medusa_overflow_check(span, context);
+                context.assembly_text += "push rax\n";
+            } else if datatype == VariableDataType::FLOAT {
+                context.assembly_text += "
 pop rbx
 movq xmm2, rbx
 pop rax
@@ -406,19 +567,16 @@ mulpd xmm1, xmm2
 movq rax, xmm1
 push rax
 ";
-                }
-
-                stack.push(datatype);
             }
-            Rule::divide => {
-                // Pop the top two numbers off the stack
-                stack.pop();
-                let datatype = stack.pop().unwrap();
-
-                if datatype == VariableDataType::INT {
-                    context.assembly_text += "pop rbx\npop rax\nxor rdx, rdx\nidiv rbx\npush rax\n";
-                } else if datatype == VariableDataType::FLOAT {
-                    context.assembly_text += "
+        }
+        BinaryOperator::Divide => {
+            if datatype == VariableDataType::INT {
+                context.assembly_text += "pop rbx\npop rax\ncmp rbx, 0\n";
+                medusa_runtime_error_check(RuntimeError::DivisionByZero, span, "jne", context);
+                medusa_signed_division(true, span, context);
+                context.assembly_text += "push rax\n";
+            } else if datatype == VariableDataType::FLOAT {
+                context.assembly_text += "
 pop rbx
 movq xmm2, rbx
 pop rax
@@ -427,526 +585,582 @@ divpd xmm1, xmm2
 movq rax, xmm1
 push rax
 ";
-                }
-
-                stack.push(datatype);
             }
-            Rule::modulo => {
-                // Pop the top two numbers off the stack
-                stack.pop();
-                let datatype = stack.pop().unwrap();
-
-                if datatype == VariableDataType::INT {
-                    context.assembly_text += "pop rbx\npop rax\nxor rdx, rdx\nidiv rbx\npush rdx\n";
-                } else if datatype == VariableDataType::FLOAT {
-                    panic!("Modulo is not supported for floats")
+        }
+        BinaryOperator::Modulo => {
+            context.assembly_text += "pop rbx\npop rax\ncmp rbx, 0\n";
+            medusa_runtime_error_check(RuntimeError::DivisionByZero, span, "jne", context);
+            medusa_signed_division(false, span, context);
+            context.assembly_text += "push rdx\n";
+        }
+        BinaryOperator::Concatenate => unreachable!("see medusa_parse_concatenation"),
+        BinaryOperator::Power => {
+            if datatype == VariableDataType::INT {
+                print_assembly_with_context("compute_integer_power", context);
+
+                // The routine can't stop at the first overflow without giving up on being a plain loop, so
+                // it only records whether one happened
+                if context.overflow_checks {
+                    context.assembly_text += "cmp r9, 0\n";
+                    medusa_runtime_error_check(RuntimeError::IntegerOverflow, span, "je", context);
                 }
-
-                stack.push(datatype);
+            } else if datatype == VariableDataType::FLOAT {
+                print_assembly_with_context("compute_float_power", context);
             }
-            Rule::concatenate => {
-                // Pop the top two strings off the stack
-                stack.pop();
-                stack.pop();
-
-                print_assembly_with_context("concatenate_strings", context);
+        }
+    }
+}
 
-                stack.push(VariableDataType::STRING);
-            }
-            Rule::power => {
-                // Pop the top two strings off the stack
-                stack.pop();
-                let datatype = stack.pop().unwrap();
-
-                if datatype == VariableDataType::INT {
-                    print_assembly_with_context("compute_integer_power", context);
-                    stack.push(VariableDataType::INT);
-                } else {
-                    todo!();
-                    print_assembly_with_context("compute_float_power", context);
-                    stack.push(VariableDataType::FLOAT);
-                }
-            }
-            Rule::cast => 'cast: {
-                let from_datatype = stack.pop().unwrap();
-                let to_datatype = match token.as_span().as_str() {
-                    "(int)" => VariableDataType::INT,
-                    "(float)" => VariableDataType::FLOAT,
-                    "(string)" => VariableDataType::STRING,
-                    _ => unreachable!(),
-                };
+/// Divides RAX by RBX (which isn't 0), leaving the quotient in RAX and the remainder in RDX. idiv faults
+/// when the quotient doesn't fit, which only happens for the smallest int divided by -1, so dividing by -1
+/// is done by hand: the quotient is the negated dividend (which wraps around for the smallest int), and the
+/// remainder is always 0. With overflow checks on, dividing the smallest int by -1 is an overflow.
+fn medusa_signed_division(check_quotient: bool, span: Span, context: &mut CompilerContext) {
+    let divide_label = context.label_index;
+    context.label_index += 1;
 
-                if from_datatype == to_datatype {
-                    // This is useless code - no conversion necessary
-                    break 'cast;
-                }
+    let done_label = context.label_index;
+    context.label_index += 1;
 
-                match from_datatype {
-                    VariableDataType::INT => match to_datatype {
-                        VariableDataType::FLOAT => {
-                            medusa_int_to_float(context);
-                        }
-                        VariableDataType::STRING => {
-                            medusa_int_to_string(context);
-                        }
-                        _ => unreachable!(),
-                    },
-                    VariableDataType::FLOAT => match to_datatype {
-                        VariableDataType::INT => {
-                            medusa_float_to_int(context);
-                        }
-                        VariableDataType::STRING => {
-                            medusa_float_to_string(context);
-                        }
-                        _ => unreachable!(),
-                    },
-                    VariableDataType::STRING => match to_datatype {
-                        VariableDataType::INT => {
-                            medusa_string_to_int(context);
-                        }
-                        VariableDataType::FLOAT => {
-                            medusa_string_to_float(context);
-                        }
-                        _ => unreachable!(),
-                    },
-                }
+    context.assembly_text +=
+        &format!("cmp rbx, -1\njne label_{divide_label}\nxor rdx, rdx\nneg rax\n");
 
-                stack.push(to_datatype);
-            }
-            _ => {}
-        }
+    // The remainder is 0 even when the quotient overflows, so only division is checked
+    if check_quotient {
+        medusa_overflow_check(span, context);
     }
 
-    return stack.pop().unwrap();
+    context.assembly_text += &format!(
+        "jmp label_{done_label}\nlabel_{divide_label}:\ncqo\nidiv rbx\nlabel_{done_label}:\n"
+    );
 }
 
-fn medusa_parse_declaration(pair: pest::iterators::Pair<Rule>, context: &mut CompilerContext) {
-    let mut pairs = pair.into_inner();
+/// Concatenates the two strings on top of the stack, freeing whichever of them are temporaries.
+fn medusa_parse_concatenation(
+    left: &Expression,
+    right: &Expression,
+    context: &mut CompilerContext,
+) {
+    context.assembly_text += "pop r8\npop rdx\ncall medusa_concatenate_strings\n";
 
-    let datatype = match pairs.next().unwrap().as_span().as_str() {
-        "int" => VariableDataType::INT,
-        "string" => VariableDataType::STRING,
-        "float" => VariableDataType::FLOAT,
-        _ => unreachable!(),
-    };
+    if is_temporary_string(left) {
+        context.assembly_text += "call medusa_free_string\n";
+    }
 
-    // There are three options for a declaration:
-    //  1. Datatype and identifier
-    //  2. Datatype and assignment
-    //  3. Datatype and input statement
-    // We need to figure out which option is being used
+    if is_temporary_string(right) {
+        context.assembly_text += "mov rdx, r8\ncall medusa_free_string\n";
+    }
 
-    let pair = pairs.next().unwrap();
+    context.assembly_text += "push rax\n";
+}
 
-    if pair.as_rule() == Rule::identifier {
-        // This is option 1
-        let name = pair.as_span().as_str().to_string();
+fn medusa_parse_declaration(declaration: &Declaration, context: &mut CompilerContext) {
+    // Storage for every variable is emitted at the end of the program, so a declaration only has to give
+    // the variable its starting value. A declaration inside of a loop runs once per iteration, and each
+    // iteration should start off with a fresh variable. A starting value can't refer to the variable being
+    // declared (the checker resolves it to an outer variable), so storing it is enough.
+    match &declaration.value {
+        DeclarationValue::None => match declaration.datatype {
+            VariableDataType::STRING => {
+                // The variable keeps its string (and its capacity), but the string is emptied
+                let memory = medusa_variable_memory(&declaration.name, context);
+                context.assembly_text +=
+                    &format!("mov rax, {memory}\nmov qword [rax - 8], 0\nmov byte [rax], 0\n");
+            }
+            _ => {
+                let memory = medusa_variable_memory(&declaration.name, context);
+                context.assembly_text += &format!("mov qword {memory}, 0\n");
+            }
+        },
+        DeclarationValue::Assignment(assignment) => medusa_parse_assignment(assignment, context),
+        DeclarationValue::Input(input) => medusa_parse_input(input, context),
+    }
+}
 
-        context.variables.insert(name.clone(), datatype);
-    } else if pair.as_rule() == Rule::assignment {
-        // This is option 2
+fn medusa_parse_array_declaration(declaration: &ArrayDeclaration, context: &mut CompilerContext) {
+    medusa_parse_expression(&declaration.length, context);
 
-        // Clone the pair and peek into it so we can grab the identifier of the variable from the input statement
-        // before evaluating the input statement.
+    context.assembly_text += "pop rbx\ncmp rbx, 0\n";
+    medusa_runtime_error_check(
+        RuntimeError::NegativeArrayLength,
+        declaration.length.span,
+        "jge",
+        context,
+    );
 
-        let name = pair
-            .clone()
-            .into_inner()
-            .peek()
-            .unwrap()
-            .as_span()
-            .as_str()
-            .to_string();
+    // The array from the last time this declaration ran (if it has run before) is replaced by a new one
+    medusa_free_array(&declaration.name, declaration.element_datatype, context);
 
-        context.variables.insert(name.clone(), datatype);
+    // Make room for the length and the elements - the memory is zeroed, so every int, float, and bool
+    // starts off as 0
+    context.assembly_text += "
+; Allocate the array
+lea rcx, [rbx * 8 + 8]
+call medusa_alloc
+mov [rax], rbx
+";
 
-        medusa_parse_assignment(pair, context);
-    } else {
-        // This is option 3
+    if declaration.element_datatype == VariableDataType::STRING {
+        // Every string element gets a string of its own, just like a string variable
+        let loop_index = context.label_index;
+        context.label_index += 1;
 
-        // Clone the pair and peek into it so we can grab the identifier of the variable from the input statement
-        // before evaluating the input statement.
+        let break_index = context.label_index;
+        context.label_index += 1;
 
-        let name = pair
-            .clone()
-            .into_inner()
-            .peek()
-            .unwrap()
-            .as_span()
-            .as_str()
-            .to_string();
+        context.assembly_text += &format!(
+            "
+; Give every element an empty string
+mov r9, rax
+xor r10, r10
 
-        context.variables.insert(name.clone(), datatype);
+label_{loop_index}:
+cmp r10, rbx
+jge label_{break_index}
 
-        medusa_parse_input(pair, context);
-    }
-}
+xor rcx, rcx
+call medusa_string_new
+mov [r9 + r10 * 8 + 8], rax
+inc r10
+jmp label_{loop_index}
 
-fn medusa_parse_assignment(pair: pest::iterators::Pair<Rule>, context: &mut CompilerContext) {
-    let mut pairs = pair.into_inner();
+label_{break_index}:
+mov rax, r9
+"
+        );
+    }
 
-    let name = pairs.next().unwrap().as_span().as_str().to_string();
+    let memory = medusa_variable_memory(&declaration.name, context);
+    context.assembly_text += &format!("mov {memory}, rax\n");
+}
 
-    let datatype = match context.variables.get(&name) {
-        Some(x) => *x,
-        None => panic!("Variable does not exist for input"),
+/// Frees the array in a variable, along with every one of its strings if it's a string array. The variable
+/// holds 0 until the array's declaration has run, in which case nothing is freed.
+fn medusa_free_array(
+    name: &str,
+    element_datatype: VariableDataType,
+    context: &mut CompilerContext,
+) {
+    let memory = medusa_variable_memory(name, context);
+    let routine = match element_datatype {
+        VariableDataType::STRING => "medusa_free_string_array",
+        _ => "medusa_free_array",
     };
 
-    if let Some(expression) = pairs.next() {
-        if medusa_parse_expression(expression, context) != datatype {
-            panic!("Mismatched datatypes on declaration")
-        };
+    context.assembly_text += &format!("mov rcx, {memory}\ncall {routine}\n");
+}
 
-        match datatype {
-            VariableDataType::INT | VariableDataType::FLOAT => {
-                context.assembly_text += format!("pop qword [rel var_{}]\n", name).as_str();
-            }
-            VariableDataType::STRING => {
-                let loop_index = context.label_index;
-                context.label_index += 1;
+fn medusa_parse_element_assignment(assignment: &ElementAssignment, context: &mut CompilerContext) {
+    medusa_parse_expression(&assignment.index, context);
+    medusa_parse_expression(&assignment.value, context);
 
-                let break_index = context.label_index;
-                context.label_index += 1;
+    // The value is on top of the stack, with the index just below it
+    context.assembly_text += "pop r8\n";
+    medusa_index_array(&assignment.name, assignment.index.span, context);
 
-                context.assembly_text += format!(
-                    "
-; Copy the string
-mov rcx, 0
-pop r8
-lea rax, [rel var_{name}]
+    match assignment.value.datatype {
+        VariableDataType::STRING => {
+            // Copy the string into the element's own string
+            context.assembly_text += "lea rdx, [rax + rbx * 8 + 8]\ncall medusa_assign_string\n";
+            medusa_free_stored_string(is_temporary_string(&assignment.value), context);
+        }
+        _ => {
+            context.assembly_text += "mov [rax + rbx * 8 + 8], r8\n";
+        }
+    }
+}
 
-label_{loop_index}:
-mov dl, [r8 + rcx]
-cmp dl, 0
-jz label_{break_index}
+fn medusa_parse_assignment(assignment: &Assignment, context: &mut CompilerContext) {
+    medusa_parse_expression(&assignment.value, context);
+    medusa_store_variable(
+        &assignment.name,
+        assignment.value.datatype,
+        is_temporary_string(&assignment.value),
+        context,
+    );
+}
 
-mov [rax + rcx], dl
-inc rcx
-jmp label_{loop_index}
+/// Returns the memory that holds a variable: a slot in the current stack frame for variables that belong
+/// to a function, or the variable's storage in the data section for everything else. String variables hold
+/// a pointer to their string.
+fn medusa_variable_memory(name: &str, context: &CompilerContext) -> String {
+    match context.local_variables.get(name) {
+        Some(offset) => format!("[rbp - {offset}]"),
+        None => format!("[rel var_{name}]"),
+    }
+}
 
-label_{break_index}:
-"
-                )
-                .as_str();
-            }
+/// Pops a value off the stack and stores it into the variable. Strings are copied into the variable's own
+/// string, which grows whenever the new value doesn't fit, so that the variable never shares its contents
+/// with another string. A temporary string is freed once it has been copied.
+fn medusa_store_variable(
+    name: &str,
+    datatype: VariableDataType,
+    temporary: bool,
+    context: &mut CompilerContext,
+) {
+    match datatype {
+        VariableDataType::INT | VariableDataType::FLOAT | VariableDataType::BOOL => {
+            let memory = medusa_variable_memory(name, context);
+            context.assembly_text += format!("pop qword {memory}\n").as_str();
         }
-    };
+        VariableDataType::STRING => {
+            let memory = medusa_variable_memory(name, context);
+            context.assembly_text +=
+                &format!("pop r8\nlea rdx, {memory}\ncall medusa_assign_string\n");
+            medusa_free_stored_string(temporary, context);
+        }
+    }
 }
 
-fn medusa_parse_output(pair: pest::iterators::Pair<Rule>, context: &mut CompilerContext) {
-    let expression_pair = pair.into_inner().next().unwrap();
-
-    let datatype = medusa_parse_expression(expression_pair, context);
+/// Frees the string in R8 after medusa_assign_string has copied it, if it's a temporary.
+fn medusa_free_stored_string(temporary: bool, context: &mut CompilerContext) {
+    if temporary {
+        context.assembly_text += "mov rdx, r8\ncall medusa_free_string\n";
+    }
+}
 
-    /*if datatype != VariableDataType::STRING {
-        panic!("Expected string for output");
-    }*/
+fn medusa_parse_output(output: &Output, context: &mut CompilerContext) {
+    medusa_parse_expression(&output.value, context);
 
     // If the datatype isn't a string, convert it to a string
-    match datatype {
+    match output.value.datatype {
         VariableDataType::INT => {
             medusa_int_to_string(context);
         }
         VariableDataType::FLOAT => {
             medusa_float_to_string(context);
         }
+        VariableDataType::BOOL => {
+            medusa_bool_to_string(context);
+        }
         VariableDataType::STRING => {
             // Do nothing
         }
     }
 
-    let loop_index = context.label_index;
-    context.label_index += 1;
-
-    let break_index = context.label_index;
-    context.label_index += 1;
-
-    context.assembly_text += format!(
-        "
-; find string length
+    context.assembly_text += "
+; print string
 pop rdx
-xor r8, r8
-
-label_{loop_index}:
-mov al, [rdx + r8]
-cmp al, 0
-jz label_{break_index}
-inc r8
-jmp label_{loop_index}
+mov r8, [rdx - 8]
+call medusa_write
+";
 
-label_{break_index}:
+    // Everything but a string has just been converted into a temporary string
+    if output.value.datatype != VariableDataType::STRING || is_temporary_string(&output.value) {
+        context.assembly_text += "call medusa_free_string\n";
+    }
 
-; print string
-sub rsp, 16
-mov rcx, [rel output_handle]
-mov r9, ignore
-call WriteFile
+    context.assembly_text += "
 
-mov rcx, [rel output_handle]
 lea rdx, [rel newline]
 mov r8, 1
-mov r9, ignore
-call WriteFile
-add rsp, 16
-"
-    )
-    .as_str();
+call medusa_write
+";
 }
 
-fn medusa_parse_input(pair: pest::iterators::Pair<Rule>, context: &mut CompilerContext) {
-    let identifier = pair.into_inner().next().unwrap().as_span().as_str();
-
-    print_assembly_with_context("input", context);
-
-    let datatype = match context.variables.get(identifier) {
-        Some(x) => x,
-        None => panic!("Variable does not exist for input"),
-    };
+fn medusa_parse_input(input: &Input, context: &mut CompilerContext) {
+    // The line that was read is a temporary string
+    context.assembly_text += "call medusa_read_line\npush rax\n";
 
     // If the datatype of our variable isn't a string, convert the input accordingly before storing
-    match datatype {
-        VariableDataType::INT => {
-            medusa_string_to_int(context);
-            context.assembly_text += format!(
-                "
-; Store the input into the variable
-pop qword [rel var_{identifier}]
-"
-            )
-            .as_str();
-        }
-        VariableDataType::FLOAT => {
-            medusa_string_to_float(context);
-            context.assembly_text += format!(
-                "
-; Store the input into the variable
-pop qword [rel var_{identifier}]
-"
-            )
-            .as_str();
+    match input.datatype {
+        VariableDataType::INT | VariableDataType::FLOAT => {
+            medusa_convert_string(input.datatype, true, context);
         }
         VariableDataType::STRING => {
-            // Copy the string into the variable
-            let loop_index = context.label_index;
-            context.label_index += 1;
+            // Do nothing
+        }
+        // The checker doesn't allow bools to be read from input
+        VariableDataType::BOOL => unreachable!(),
+    }
 
-            let break_index = context.label_index;
-            context.label_index += 1;
+    // Store the input into the variable
+    medusa_store_variable(&input.name, input.datatype, true, context);
 
-            context.assembly_text += format!(
-                "
-; Copy the string from the stack into our variable
-pop rax
-mov rcx, 0
-lea rbx, [rel var_{identifier}]
+    // The conversion leaves RCX set to 0 if the input wasn't a valid number
+    if input.datatype == VariableDataType::STRING {
+        return;
+    }
 
-label_{loop_index}:
-mov dl, [rax + rcx]
-cmp dl, 0
-jz label_{break_index}
+    context.assembly_text += "cmp rcx, 0\n";
 
-mov [rbx + rcx], dl
-inc rcx
-jmp label_{loop_index}
+    match &input.else_body {
+        Some(else_body) => {
+            let valid_label = context.label_index;
+            context.label_index += 1;
 
-label_{break_index}:
-"
-            )
-            .as_str();
+            context.assembly_text += &format!("jne label_{valid_label}\n");
+
+            for statement in else_body.iter() {
+                medusa_parse_statement(statement, context);
+            }
+
+            context.assembly_text += &format!("label_{valid_label}:\n");
         }
+        None => medusa_runtime_error_check(
+            RuntimeError::InvalidInput(input.datatype),
+            input.span,
+            "jne",
+            context,
+        ),
     }
-
-    // Store the input into the variable !!!NO!!!
-    /*context.assembly_text += format!("
-    ; Store the input into the variable
-    mov [rel var_{identifier}], rax
-    ").as_str();*/
 }
 
+/// Evaluates a bool expression, jumping to the label if it's false and falling through if it's true.
+/// Comparisons and logical operators jump directly, without ever putting a bool on the stack, which is
+/// also what makes && and || skip their right operand when the left one decides the result.
 fn medusa_parse_condition(
-    pair: pest::iterators::Pair<Rule>,
+    condition: &Expression,
     context: &mut CompilerContext,
     jump_if_false_label_index: u64,
 ) {
-    let mut pairs = pair.into_inner();
+    match &condition.kind {
+        ExpressionKind::Comparison {
+            operator,
+            left,
+            right,
+        } => medusa_parse_comparison(*operator, left, right, context, jump_if_false_label_index),
+        ExpressionKind::Logical {
+            operator: LogicalOperator::And,
+            left,
+            right,
+        } => {
+            // If either side is false, the whole thing is false
+            medusa_parse_condition(left, context, jump_if_false_label_index);
+            medusa_parse_condition(right, context, jump_if_false_label_index);
+        }
+        ExpressionKind::Logical {
+            operator: LogicalOperator::Or,
+            left,
+            right,
+        } => {
+            // If the left side is true, the whole thing is true - otherwise, it all comes down to the right side
+            let check_right_label = context.label_index;
+            context.label_index += 1;
+
+            let true_label = context.label_index;
+            context.label_index += 1;
+
+            medusa_parse_condition(left, context, check_right_label);
+            context.assembly_text +=
+                &format!("jmp label_{true_label}\nlabel_{check_right_label}:\n");
 
-    let left_expression_pair = pairs.next().unwrap();
-    let conditional_operator_pair = pairs.next().unwrap();
-    let right_expression_pair = pairs.next().unwrap();
+            medusa_parse_condition(right, context, jump_if_false_label_index);
+            context.assembly_text += &format!("label_{true_label}:\n");
+        }
+        ExpressionKind::Not(operand) => {
+            // The operand being false is the only way to get past the jump
+            let operand_false_label = context.label_index;
+            context.label_index += 1;
 
-    let left_datatype = medusa_parse_expression(left_expression_pair, context);
-    let right_datatype = medusa_parse_expression(right_expression_pair, context);
+            medusa_parse_condition(operand, context, operand_false_label);
+            context.assembly_text +=
+                &format!("jmp label_{jump_if_false_label_index}\nlabel_{operand_false_label}:\n");
+        }
+        _ => {
+            // Literals and variables are stored as 0 or 1
+            medusa_parse_expression(condition, context);
 
-    if left_datatype != right_datatype {
-        panic!("Datatypes in if statement do not match!");
+            context.assembly_text +=
+                &format!("pop rax\ncmp rax, 0\nje label_{jump_if_false_label_index}\n");
+        }
     }
+}
+
+/// Compares the two operands, jumping to the label if the comparison is false.
+fn medusa_parse_comparison(
+    operator: ConditionalOperator,
+    left: &Expression,
+    right: &Expression,
+    context: &mut CompilerContext,
+    jump_if_false_label_index: u64,
+) {
+    medusa_parse_expression(left, context);
+    medusa_parse_expression(right, context);
 
     // The result of the right expression is on top of the stack, and the result of the left expression is just below it
     // Let's store left in RAX and right in RBX
     context.assembly_text += "pop rbx\npop rax\n";
 
-    let conditional_operator = conditional_operator_pair.as_span().as_str();
+    match left.datatype {
+        VariableDataType::INT | VariableDataType::BOOL => {
+            let jump_instruction = signed_jump_if_false(operator);
 
-    match conditional_operator {
-        ">" => match left_datatype {
-            VariableDataType::INT => {
-                context.assembly_text +=
-                    &format!("cmp rax, rbx\njle label_{jump_if_false_label_index}\n")
-            }
-            VariableDataType::FLOAT => {
-                todo!()
-            }
-            VariableDataType::STRING => {
-                panic!("Cannot compare strings with >");
-            }
-        },
-        "<" => match left_datatype {
-            VariableDataType::INT => {
-                context.assembly_text +=
-                    &format!("cmp rax, rbx\njge label_{jump_if_false_label_index}\n")
-            }
-            VariableDataType::FLOAT => {
-                todo!()
-            }
-            VariableDataType::STRING => {
-                panic!("Cannot compare strings with <");
-            }
-        },
-        ">=" => match left_datatype {
-            VariableDataType::INT => {
-                context.assembly_text +=
-                    &format!("cmp rax, rbx\njl label_{jump_if_false_label_index}\n")
-            }
-            VariableDataType::FLOAT => {
-                todo!()
-            }
-            VariableDataType::STRING => {
-                panic!("Cannot compare strings with >=");
-            }
-        },
-        "<=" => match left_datatype {
-            VariableDataType::INT => {
-                context.assembly_text +=
-                    &format!("cmp rax, rbx\njg label_{jump_if_false_label_index}\n")
-            }
-            VariableDataType::FLOAT => {
-                todo!()
-            }
-            VariableDataType::STRING => {
-                panic!("Cannot compare strings with <=");
-            }
-        },
-        "==" => match left_datatype {
-            VariableDataType::INT => {
-                context.assembly_text +=
-                    &format!("cmp rax, rbx\njne label_{jump_if_false_label_index}\n")
-            }
-            VariableDataType::FLOAT => {
-                todo!()
-            }
-            VariableDataType::STRING => {
-                todo!()
-            }
-        },
-        "!=" => match left_datatype {
-            VariableDataType::INT => {
-                context.assembly_text +=
-                    &format!("cmp rax, rbx\nje label_{jump_if_false_label_index}\n")
-            }
-            VariableDataType::FLOAT => {
-                todo!()
+            context.assembly_text +=
+                &format!("cmp rax, rbx\n{jump_instruction} label_{jump_if_false_label_index}\n");
+        }
+        VariableDataType::FLOAT => {
+            medusa_parse_float_condition(operator, context, jump_if_false_label_index);
+        }
+        VariableDataType::STRING => {
+            // medusa_compare_strings returns -1, 0, or 1, which can be compared against 0 like an int
+            let jump_instruction = signed_jump_if_false(operator);
+
+            context.assembly_text += "mov rdx, rax\nmov r8, rbx\ncall medusa_compare_strings\n";
+
+            if is_temporary_string(left) {
+                context.assembly_text += "call medusa_free_string\n";
             }
-            VariableDataType::STRING => {
-                todo!()
+
+            if is_temporary_string(right) {
+                context.assembly_text += "mov rdx, r8\ncall medusa_free_string\n";
             }
-        },
-        _ => {
-            unreachable!();
+
+            context.assembly_text +=
+                &format!("cmp rax, 0\n{jump_instruction} label_{jump_if_false_label_index}\n");
         }
     }
 }
 
-fn medusa_parse_if(pair: pest::iterators::Pair<Rule>, context: &mut CompilerContext) {
-    let mut pairs = pair.into_inner();
-
-    let skip_if_statement_label = context.label_index;
-    context.label_index += 1;
+/// Returns the jump instruction that skips over the code guarded by a signed comparison. The jump is taken
+/// when the condition is false, so each operator jumps on its opposite.
+fn signed_jump_if_false(operator: ConditionalOperator) -> &'static str {
+    match operator {
+        ConditionalOperator::Greater => "jle",
+        ConditionalOperator::Less => "jge",
+        ConditionalOperator::GreaterOrEqual => "jl",
+        ConditionalOperator::LessOrEqual => "jg",
+        ConditionalOperator::Equal => "jne",
+        ConditionalOperator::NotEqual => "je",
+    }
+}
 
-    let condition_pair = pairs.next().unwrap();
-    medusa_parse_condition(condition_pair, context, skip_if_statement_label);
+/// Compares the floats in RAX (left) and RBX (right), jumping to the label if the condition is false.
+///
+/// ucomisd sets CF when left < right, ZF when they're equal, and all three of ZF, PF, and CF when either one is
+/// NaN. Only the unsigned-style jumps (above/below) are used, so that an unordered comparison always lands on
+/// the false side - except for != which, like IEEE 754 says, is true when either side is NaN.
+fn medusa_parse_float_condition(
+    operator: ConditionalOperator,
+    context: &mut CompilerContext,
+    jump_if_false_label_index: u64,
+) {
+    context.assembly_text += "movq xmm1, rax\nmovq xmm2, rbx\n";
 
-    let mut else_statement: Option<Pair<Rule>> = None;
+    match operator {
+        ConditionalOperator::Greater => {
+            context.assembly_text +=
+                &format!("ucomisd xmm1, xmm2\njbe label_{jump_if_false_label_index}\n");
+        }
+        ConditionalOperator::Less => {
+            // left < right is the same as right > left
+            context.assembly_text +=
+                &format!("ucomisd xmm2, xmm1\njbe label_{jump_if_false_label_index}\n");
+        }
+        ConditionalOperator::GreaterOrEqual => {
+            context.assembly_text +=
+                &format!("ucomisd xmm1, xmm2\njb label_{jump_if_false_label_index}\n");
+        }
+        ConditionalOperator::LessOrEqual => {
+            // left <= right is the same as right >= left
+            context.assembly_text +=
+                &format!("ucomisd xmm2, xmm1\njb label_{jump_if_false_label_index}\n");
+        }
+        ConditionalOperator::Equal => {
+            // Equal only if ZF is set and the comparison wasn't unordered
+            context.assembly_text += &format!(
+                "ucomisd xmm1, xmm2\njne label_{jump_if_false_label_index}\njp label_{jump_if_false_label_index}\n"
+            );
+        }
+        ConditionalOperator::NotEqual => {
+            // Not equal if ZF is clear, or if the comparison was unordered
+            let not_equal_label = context.label_index;
+            context.label_index += 1;
 
-    // Print all of the code inside the if statement
-    loop {
-        match pairs.next() {
-            Some(pair) => {
-                if pair.as_rule() == Rule::else_ {
-                    else_statement = Some(pair);
-                    break;
-                } else {
-                    medusa_parse_statement(pair, context);
-                }
-            }
-            None => {
-                break;
-            }
+            context.assembly_text += &format!(
+                "ucomisd xmm1, xmm2\njp label_{not_equal_label}\nje label_{jump_if_false_label_index}\nlabel_{not_equal_label}:\n"
+            );
         }
     }
+}
 
-    match else_statement {
-        Some(else_statement) => {
-            // This if statement has an else statement tagging along with it
+fn medusa_parse_if(if_statement: &If, context: &mut CompilerContext) {
+    // Every branch that runs jumps to this label afterwards, skipping all of the branches below it
+    let end_of_if_statement_label = context.label_index;
+    context.label_index += 1;
 
-            let skip_else_statement_label = context.label_index;
-            context.label_index += 1;
+    // The if statement and each else if are all compiled the same way: check the condition,
+    // and skip to the next branch if it's false
+    let branches = std::iter::once((&if_statement.condition, &if_statement.body)).chain(
+        if_statement
+            .else_ifs
+            .iter()
+            .map(|else_if| (&else_if.condition, &else_if.body)),
+    );
+    let branch_count = 1 + if_statement.else_ifs.len();
 
-            // The instructions are ordered in this way so that, if the if statement is true,
-            // it will hit the jump and skip all of the else statement code
-            context.assembly_text += &format!("\njmp label_{skip_else_statement_label}\n");
-            context.assembly_text += &format!("\nlabel_{skip_if_statement_label}:\n");
-
-            // Print all of the code inside the else statement
-            let mut else_statement_pairs = else_statement.into_inner();
-            loop {
-                match else_statement_pairs.next() {
-                    Some(pair) => {
-                        medusa_parse_statement(pair, context);
-                    }
-                    None => {
-                        break;
-                    }
-                }
-            }
+    for (index, (condition, body)) in branches.enumerate() {
+        let skip_branch_label = context.label_index;
+        context.label_index += 1;
+
+        medusa_parse_condition(condition, context, skip_branch_label);
 
-            context.assembly_text += &format!("\nlabel_{skip_else_statement_label}:\n");
+        // Print all of the code inside the branch
+        for statement in body.iter() {
+            medusa_parse_statement(statement, context);
         }
-        None => {
-            // Just a normal if statement - emit the ending label
 
-            context.assembly_text += &format!("\nlabel_{skip_if_statement_label}:\n");
+        // The last branch doesn't need to jump, since the end label comes right after it
+        let is_last_branch = index + 1 == branch_count && if_statement.else_body.is_none();
+        if !is_last_branch {
+            context.assembly_text += &format!("\njmp label_{end_of_if_statement_label}\n");
         }
+
+        context.assembly_text += &format!("\nlabel_{skip_branch_label}:\n");
     }
+
+    // If none of the conditions were true, we fall through to the else statement
+    if let Some(else_body) = &if_statement.else_body {
+        for statement in else_body.iter() {
+            medusa_parse_statement(statement, context);
+        }
+    }
+
+    context.assembly_text += &format!("\nlabel_{end_of_if_statement_label}:\n");
 }
 
-fn medusa_parse_forloop(pair: pest::iterators::Pair<Rule>, context: &mut CompilerContext) {
-    let mut pairs = pair.into_inner();
+fn medusa_parse_forloop(forloop: &ForLoop, context: &mut CompilerContext) {
+    if let Some(initializer) = &forloop.initializer {
+        medusa_parse_statement(initializer, context);
+    }
 
-    let first_pair = pairs.next().unwrap();
+    let loop_label = context.label_index;
+    context.label_index += 1;
 
-    match first_pair.as_rule() {
-        Rule::declaration => medusa_parse_declaration(first_pair, context),
-        Rule::assignment => medusa_parse_assignment(first_pair, context),
-        Rule::identifier => {
-            // Do absolutely nothing, because this language is weird >:(
-        }
-        _ => unreachable!(),
+    let break_loop_label = context.label_index;
+    context.label_index += 1;
+
+    let step_label = context.label_index;
+    context.label_index += 1;
+
+    // This is the top of the loop, emit our label here
+    context.assembly_text += &format!("\nlabel_{loop_label}:\n");
+
+    // Check the condition - if it's true, break the loop, otherwise, continue
+    medusa_parse_condition(&forloop.condition, context, break_loop_label);
+
+    // Print all of the code inside the loop body. Continuing skips to the step, not the condition.
+    context.loop_labels.push(LoopLabels {
+        continue_label: step_label,
+        break_label: break_loop_label,
+    });
+
+    for statement in forloop.body.iter() {
+        medusa_parse_statement(statement, context);
     }
 
-    let condition_pair = pairs.next().unwrap();
+    context.loop_labels.pop();
 
-    let assignment_pair = pairs.next().unwrap();
+    // Run the assignment at the end of the loop, then jump back to the top
+    context.assembly_text += &format!("label_{step_label}:\n");
+    medusa_parse_assignment(&forloop.step, context);
+    context.assembly_text += &format!("\njmp label_{loop_label}\n");
 
+    context.assembly_text += &format!("label_{break_loop_label}:\n");
+}
+
+fn medusa_parse_while(while_loop: &While, context: &mut CompilerContext) {
     let loop_label = context.label_index;
     context.label_index += 1;
 
@@ -956,90 +1170,291 @@ fn medusa_parse_forloop(pair: pest::iterators::Pair<Rule>, context: &mut Compile
     // This is the top of the loop, emit our label here
     context.assembly_text += &format!("\nlabel_{loop_label}:\n");
 
-    // Check the condition - if it's true, break the loop, otherwise, continue
-    medusa_parse_condition(condition_pair, context, break_loop_label);
+    // Check the condition - if it's false, break the loop, otherwise, run the body
+    medusa_parse_condition(&while_loop.condition, context, break_loop_label);
 
     // Print all of the code inside the loop body
-    loop {
-        match pairs.next() {
-            Some(pair) => {
-                medusa_parse_statement(pair, context);
-            }
-            None => {
-                break;
-            }
-        }
+    context.loop_labels.push(LoopLabels {
+        continue_label: loop_label,
+        break_label: break_loop_label,
+    });
+
+    for statement in while_loop.body.iter() {
+        medusa_parse_statement(statement, context);
     }
 
-    // Run the assignment at the end of the loop, then jump back to the top
-    medusa_parse_assignment(assignment_pair, context);
+    context.loop_labels.pop();
+
+    // Jump back to the top to check the condition again
     context.assembly_text += &format!("\njmp label_{loop_label}\n");
 
     context.assembly_text += &format!("label_{break_loop_label}:\n");
 }
 
-fn medusa_parse_statement(pair: pest::iterators::Pair<Rule>, context: &mut CompilerContext) {
-    match pair.as_rule() {
-        Rule::declaration => {
-            medusa_parse_declaration(pair, context);
+/// Functions are called with their arguments on the stack, pushed from first to last, and return their
+/// value using RAX. The caller removes the arguments from the stack afterwards. Every variable in the
+/// function, including the parameters, gets a slot in the function's stack frame, so each call (including
+/// recursive ones) has its own copy of them:
+///
+/// ```text
+/// [rbp + 16 + 8 * (n - 1)]  first argument
+/// [rbp + 16]                last argument
+/// [rbp + 8]                 return address
+/// [rbp]                     caller's RBP
+/// [rbp - 8]                 first variable slot
+/// ```
+fn medusa_parse_function(function: &Function, context: &mut CompilerContext) {
+    context.local_variables = function
+        .variables
+        .iter()
+        .enumerate()
+        .map(|(index, (name, _))| (name.clone(), (index as u64 + 1) * 8))
+        .collect();
+
+    context.local_strings = function
+        .variables
+        .iter()
+        .filter(|(_, datatype)| *datatype == VariableDataType::STRING)
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    // Arrays are stored as a pointer, so they get a slot just like any other variable
+    for (name, _) in function.arrays.iter() {
+        let offset = (context.local_variables.len() as u64 + 1) * 8;
+        context.local_variables.insert(name.clone(), offset);
+    }
+
+    context.local_arrays = function.arrays.clone();
+
+    let name = &function.name;
+    let frame_size = context.local_variables.len() * 8;
+
+    context.assembly_text += &format!(
+        "
+; ======================================
+; {name}
+; ======================================
+function_{name}:
+push rbp
+mov rbp, rsp
+sub rsp, {frame_size}
+"
+    );
+
+    // Every variable starts out empty - strings get a string of their own, just like global strings do
+    for (variable_name, datatype) in function.variables.iter() {
+        medusa_initialize_variable(variable_name, *datatype, context);
+    }
+
+    for (array_name, _) in function.arrays.iter() {
+        let memory = medusa_variable_memory(array_name, context);
+        context.assembly_text += &format!("mov qword {memory}, 0\n");
+    }
+
+    // Copy the arguments into the parameters' slots
+    let parameter_count = function.parameters.len();
+
+    for (index, parameter) in function.parameters.iter().enumerate() {
+        let argument_offset = 16 + 8 * (parameter_count - 1 - index);
+
+        context.assembly_text += &format!("push qword [rbp + {argument_offset}]\n");
+        medusa_store_variable(&parameter.name, parameter.datatype, false, context);
+    }
+
+    for statement in function.body.iter() {
+        medusa_parse_statement(statement, context);
+    }
+
+    context.local_variables.clear();
+    context.local_strings.clear();
+    context.local_arrays.clear();
+}
+
+/// Gives a variable its starting value. Every string variable owns a string of its own, which starts off
+/// empty and grows as needed.
+fn medusa_initialize_variable(
+    name: &str,
+    datatype: VariableDataType,
+    context: &mut CompilerContext,
+) {
+    let memory = medusa_variable_memory(name, context);
+
+    context.assembly_text += &match datatype {
+        VariableDataType::STRING => {
+            format!("xor rcx, rcx\ncall medusa_string_new\nmov {memory}, rax\n")
         }
-        Rule::assignment => {
-            medusa_parse_assignment(pair, context);
+        _ => format!("mov qword {memory}, 0\n"),
+    };
+}
+
+fn medusa_parse_return(return_statement: &Return, context: &mut CompilerContext) {
+    medusa_parse_expression(&return_statement.value, context);
+
+    // The caller frees the string that gets returned, so it has to be one that nothing else owns
+    let value = &return_statement.value;
+
+    if value.datatype == VariableDataType::STRING && !is_temporary_string(value) {
+        context.assembly_text += "pop rdx\ncall medusa_copy_string\n";
+    } else {
+        context.assembly_text += "pop rax\n";
+    }
+
+    // The function's strings and arrays belong to this call, so they're freed along with its stack frame
+    for name in context.local_strings.clone() {
+        let memory = medusa_variable_memory(&name, context);
+        context.assembly_text += &format!("mov rdx, {memory}\ncall medusa_free_string\n");
+    }
+
+    for (name, element_datatype) in context.local_arrays.clone() {
+        medusa_free_array(&name, element_datatype, context);
+    }
+
+    // Anything that the function left on the stack is thrown away along with its stack frame
+    context.assembly_text += "mov rsp, rbp\npop rbp\nret\n";
+}
+
+fn medusa_parse_statement(statement: &Statement, context: &mut CompilerContext) {
+    match statement {
+        Statement::Declaration(declaration) => {
+            medusa_parse_declaration(declaration, context);
         }
-        Rule::output => {
-            medusa_parse_output(pair, context);
+        Statement::ArrayDeclaration(declaration) => {
+            medusa_parse_array_declaration(declaration, context);
         }
-        Rule::input => {
-            medusa_parse_input(pair, context);
+        Statement::Assignment(assignment) => {
+            medusa_parse_assignment(assignment, context);
         }
-        Rule::if_ => {
-            medusa_parse_if(pair, context);
+        Statement::ElementAssignment(assignment) => {
+            medusa_parse_element_assignment(assignment, context);
         }
-        Rule::forloop => {
-            medusa_parse_forloop(pair, context);
+        Statement::Output(output) => {
+            medusa_parse_output(output, context);
         }
-        Rule::EOI => {}
-        _ => {
-            panic!("Unexpected rule!");
+        Statement::Input(input) => {
+            medusa_parse_input(input, context);
+        }
+        Statement::If(if_statement) => {
+            medusa_parse_if(if_statement, context);
+        }
+        Statement::ForLoop(forloop) => {
+            medusa_parse_forloop(forloop, context);
+        }
+        Statement::While(while_loop) => {
+            medusa_parse_while(while_loop, context);
+        }
+        Statement::Return(return_statement) => {
+            medusa_parse_return(return_statement, context);
+        }
+        Statement::Break(_) => {
+            // The checker only allows break and continue inside of loops
+            let labels = context.loop_labels.last().unwrap();
+            context.assembly_text += &format!("jmp label_{}\n", labels.break_label);
+        }
+        Statement::Continue(_) => {
+            let labels = context.loop_labels.last().unwrap();
+            context.assembly_text += &format!("jmp label_{}\n", labels.continue_label);
         }
     };
 }
 
+/// Compiles the source text into an executable for the platform that the compiler is running on.
 pub fn compile_from_text(source_text: &str, output_file_name: &str) -> Result<(), CompileError> {
-    let parse_result = MedusaParser::parse(Rule::program, &source_text);
+    compile_from_text_for_target(source_text, output_file_name, Target::host())
+}
 
-    let file = match parse_result {
-        Err(e) => {
-            return Err(CompileError {
-                body: format!("{:#?}", e),
-            });
-        }
-        Ok(v) => v,
-    };
+/// Compiles the source text into an executable for the given target, using whichever tools can be found
+/// on the host.
+pub fn compile_from_text_for_target(
+    source_text: &str,
+    output_file_name: &str,
+    target: Target,
+) -> Result<(), CompileError> {
+    let toolchain = Toolchain::discover(target)?;
+    compile_with_toolchain(
+        source_text,
+        output_file_name,
+        &toolchain,
+        CompileOptions::default(),
+    )
+}
+
+/// Compiles the source text into an executable, using the given toolchain to assemble and link it.
+pub fn compile_with_toolchain(
+    source_text: &str,
+    output_file_name: &str,
+    toolchain: &Toolchain,
+    options: CompileOptions,
+) -> Result<(), CompileError> {
+    let assembly_source = compile_to_assembly_with_options(source_text, toolchain.target, options)?;
+
+    fs::write(format!("{}.asm", output_file_name), assembly_source).map_err(|e| {
+        CompileError::from(Diagnostic::new(
+            ErrorKind::Io,
+            format!("Could not write assembly source file ({e})"),
+        ))
+    })?;
+
+    toolchain.assemble(output_file_name)?;
+    toolchain.link(output_file_name)
+}
+
+/// Compiles the source text into NASM assembly for the platform that the compiler is running on. No files
+/// are written and no external tools are run.
+pub fn compile_to_assembly(source_text: &str) -> Result<String, CompileError> {
+    compile_to_assembly_for_target(source_text, Target::host())
+}
+
+/// Compiles the source text into NASM assembly for the given target. No files are written and no external
+/// tools are run.
+pub fn compile_to_assembly_for_target(
+    source_text: &str,
+    target: Target,
+) -> Result<String, CompileError> {
+    compile_to_assembly_with_options(source_text, target, CompileOptions::default())
+}
+
+/// Compiles the source text into NASM assembly for the given target, generating code according to the given
+/// options. No files are written and no external tools are run.
+pub fn compile_to_assembly_with_options(
+    source_text: &str,
+    target: Target,
+    options: CompileOptions,
+) -> Result<String, CompileError> {
+    let program = check_from_text(source_text)?;
 
     let mut context = CompilerContext {
+        source_text: source_text.to_string(),
         variable_index: 0,
         label_index: 0,
         assembly_text: String::new(),
         assembly_data: String::new(),
-        variables: HashMap::new(),
+        local_variables: HashMap::new(),
+        local_strings: Vec::new(),
+        local_arrays: Vec::new(),
+        loop_labels: Vec::new(),
+        overflow_checks: options.overflow_checks,
     };
 
-    for pair in file {
-        medusa_parse_statement(pair, &mut context);
+    // Functions are emitted after the end of the program, where they can only be reached with a call
+    for function in program.functions.iter() {
+        medusa_parse_function(function, &mut context);
     }
 
-    for variable in context.variables {
-        context.assembly_data += format!(
-            "var_{}: {}\n",
-            variable.0.as_str(),
-            match variable.1 {
-                VariableDataType::INT => "dq 0",
-                VariableDataType::FLOAT => "dq 0",
-                VariableDataType::STRING => "resb 1000",
-            }
-        )
-        .as_str();
+    let function_text = std::mem::take(&mut context.assembly_text);
+
+    for (name, datatype) in program.variables.iter() {
+        medusa_initialize_variable(name, *datatype, &mut context);
+    }
+
+    for statement in program.statements.iter() {
+        medusa_parse_statement(statement, &mut context);
+    }
+
+    // The checker hands the variables over in alphabetical order, so the same program always compiles
+    // to the same assembly. Strings and arrays live on the heap, so all that's needed for them is somewhere
+    // to keep the pointer.
+    for (name, _) in program.variables.iter().chain(program.arrays.iter()) {
+        context.assembly_data += format!("var_{name}: dq 0\n").as_str();
     }
 
     let assembly_text = context.assembly_text;
@@ -1047,208 +1462,54 @@ pub fn compile_from_text(source_text: &str, output_file_name: &str) -> Result<()
 
     let medusa_version = env!("CARGO_PKG_VERSION");
 
+    // The startup code and the runtime routines (allocation, reading, writing, and exiting) are the only
+    // parts of the program that differ between targets
+    let startup = load_assembly(&format!("{}/startup", target.assembly_folder()));
+    let runtime = load_assembly(&format!("{}/runtime", target.assembly_folder()));
+
+    // Routines that are called from the generated code, and don't depend on the target
+    let routines: Vec<String> = RUNTIME_ROUTINES
+        .iter()
+        .map(|file_name| load_assembly(file_name))
+        .collect();
+    let routines = routines.join("\n");
+
     let assembly_source = format!(
         "
 bits 64
 
-global mainCRTStartup
-
-extern MessageBoxA
-extern WriteFile
-extern ReadFile
-extern GetStdHandle
-extern ExitProcess
-extern GetProcessHeap
-extern HeapAlloc
-
-section .text
-mainCRTStartup:
-
-sub rsp, 40
+{startup}
 
-mov rcx, -10
-call GetStdHandle
-mov [rel input_handle], rax
-
-mov rcx, -11
-call GetStdHandle
-mov [rel output_handle], rax
-
-call GetProcessHeap
-mov [rel heap_handle], rax
-
-sub rsp, 16
-mov rcx, [rel output_handle]
-mov rdx, medusa_string
+lea rdx, [rel medusa_string]
 mov r8, medusa_string_length
-mov r9, ignore
-call WriteFile
-add rsp, 16
+call medusa_write
 
 {assembly_text}
 
-sub rsp, 16
-mov rcx, [rel output_handle]
-mov rdx, program_ended_string
+lea rdx, [rel program_ended_string]
 mov r8, program_ended_string_length
-mov r9, ignore
-call WriteFile
-add rsp, 16
+call medusa_write
 
 mov rcx, 0
-call ExitProcess
+call medusa_exit
+
+{function_text}
 
-add rsp, 40
+{runtime}
 
-ret
+{routines}
 
 section .data
-input_handle dq 0
-output_handle dq 0
-heap_handle dq 0
 medusa_string db \"Medusa {medusa_version}\", 10, 0
 medusa_string_length equ $-medusa_string
 program_ended_string db \"Program ended\", 10, 0
 program_ended_string_length equ $-program_ended_string
 newline db 10
+true_string db \"true\", 0
+false_string db \"false\", 0
 ignore dq 0
-{assembly_data}
-
-section .bss
-buffer_string resb 1024"
+{assembly_data}"
     );
 
-    fs::write(format!("{}.asm", output_file_name), assembly_source)
-        .expect("Could not write assembly source file");
-
-    let assembler_output = Command::new("./windows/nasm.exe")
-        .args([
-            "-f win64",
-            format!("{}.asm", output_file_name).as_str(),
-            format!("-o {}.obj", output_file_name).as_str(),
-            format!("-l {}.lst", output_file_name).as_str(),
-        ])
-        .output()
-        .unwrap();
-
-    match assembler_output.status.code().unwrap() {
-        0 => {
-            let error_text = String::from_utf8(assembler_output.stderr).unwrap();
-
-            if error_text.len() > 0 {
-                println!("Assembler succeeded with warnings: {}", error_text);
-            } else {
-                println!("Assembler succeeded");
-            }
-        }
-        _ => {
-            return Err(CompileError {
-                body: format!(
-                    "Assembler failed: {}",
-                    String::from_utf8(assembler_output.stderr).unwrap()
-                ),
-            });
-        }
-    }
-
-    // Dig through the registry keys to find the exact folder path to the Windows SDK - this is
-    // necessary to link against kernel32.lib
-
-    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-    let sdk = match hklm
-        .open_subkey("SOFTWARE\\WOW6432Node\\Microsoft\\Microsoft SDKs\\Windows\\v10.0")
-    {
-        Ok(v) => v,
-        Err(_e) => {
-            return Err(CompileError {
-                body: "Could not locate registry key for Windows SDK version. Is the Windows SDK installed?".to_string()
-            });
-        }
-    };
-
-    let sdk_installation_folder: String = match sdk.get_value("InstallationFolder") {
-        Ok(v) => v,
-        Err(_e) => {
-            return Err(CompileError {
-                body: "Could not locate registry key for Windows SDK installation folder. Is the Windows SDK installed?".to_string()
-            });
-        }
-    };
-
-    let sdk_installation_version: String = match sdk.get_value("ProductVersion") {
-        Ok(v) => v,
-        Err(_e) => {
-            return Err(CompileError {
-                body: "Could not locate registry key for Windows SDK installation version. Is the Windows SDK installed?".to_string()
-            });
-        }
-    };
-
-    // We need to find the exact path to the Windows SDK - this is made difficult because the product version
-    // does not line up directly with the actual folder name, ex. the product version on my machine is
-    // 10.0.22000 while the folder path is 10.0.22000.0.
-    let paths = fs::read_dir(format!("{sdk_installation_folder}Lib\\")).unwrap();
-
-    let mut kernel32_path: Option<String> = None;
-
-    for path in paths {
-        // Strip the path down to just the folder number, ex. 10.0.22000.0
-        let path = path.unwrap().path();
-        let path = path
-            .strip_prefix(format!("{sdk_installation_folder}Lib\\"))
-            .unwrap();
-
-        // Compare it to the product version and see if everything but the last digits line up
-
-        let folder_number = path.to_str().unwrap();
-
-        // Trim the folder number so that it's the same length as the product version before comparing
-        if folder_number[..sdk_installation_version.len()] == sdk_installation_version {
-            kernel32_path = Some(format!(
-                "{sdk_installation_folder}Lib\\{folder_number}\\um\\x64\\kernel32.lib"
-            ));
-        }
-    }
-
-    let kernel32_path = match kernel32_path {
-        Some(path) => path,
-        None => {
-            return Err(CompileError {
-                body: format!("Could not locate the Windows SDK folder at {sdk_installation_folder}Lib\\{sdk_installation_version}. Is the Windows SDK installed correctly?")
-            });
-        }
-    };
-
-    let linker_output = Command::new("./windows/ld.lld.exe")
-        .args([
-            format!("{}.obj", output_file_name).as_str(),
-            format!("-o{}.exe", output_file_name).as_str(),
-            "C:/Windows/System32/user32.dll",
-            &kernel32_path,
-            //"C:/Program Files (x86)/Windows Kits/10/Lib/10.0.22000.0/um/x64/kernel32.lib",
-        ])
-        .output()
-        .unwrap();
-
-    match linker_output.status.code().unwrap() {
-        0 => {
-            let error_text = String::from_utf8(linker_output.stderr).unwrap();
-
-            if error_text.len() > 0 {
-                println!("Linker succeeded with warnings: {}", error_text);
-            } else {
-                println!("Linker succeeded");
-            }
-        }
-        _ => {
-            return Err(CompileError {
-                body: format!(
-                    "Linker failed: {}",
-                    String::from_utf8(linker_output.stderr).unwrap()
-                ),
-            });
-        }
-    }
-
-    return Ok(());
+    Ok(assembly_source)
 }
@@ -1,29 +1,193 @@
-fn main() {
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use medusa_lang::{CompileError, CompileOptions, Diagnostic, ErrorKind, Target, Toolchain};
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprint!("{}", failure.diagnostics);
+            ExitCode::from(failure.exit_code)
+        }
+    }
+}
+
+/// Why the compiler stopped early: the rendered diagnostics, and the code to exit with.
+struct Failure {
+    diagnostics: String,
+    exit_code: u8,
+}
+
+impl From<String> for Failure {
+    fn from(diagnostics: String) -> Failure {
+        Failure {
+            diagnostics,
+            exit_code: 1,
+        }
+    }
+}
+
+/// Runs the compiler (or the interpreter), returning the rendered diagnostics if anything went wrong.
+fn run() -> Result<(), Failure> {
     let args: Vec<String> = std::env::args().collect();
 
     //println!("{}", args[0]);
 
-    let input_file_name = if args.len() > 1 {
-        args[1].clone()
+    // Options are passed in as --name=value, everything else is a file name
+    let mut target = Target::host();
+    let mut assembler: Option<PathBuf> = None;
+    let mut linker: Option<PathBuf> = None;
+    let mut libraries: Vec<PathBuf> = Vec::new();
+    let mut emit_assembly_only = false;
+    let mut interpret = false;
+    let mut options = CompileOptions::default();
+    let mut file_names: Vec<String> = Vec::new();
+
+    for arg in args.iter().skip(1) {
+        if let Some(value) = arg.strip_prefix("--target=") {
+            target = value.parse().map_err(|e: CompileError| e.render("", ""))?;
+        } else if let Some(value) = arg.strip_prefix("--assembler=") {
+            assembler = Some(PathBuf::from(value));
+        } else if let Some(value) = arg.strip_prefix("--linker=") {
+            linker = Some(PathBuf::from(value));
+        } else if let Some(value) = arg.strip_prefix("--library=") {
+            libraries.push(PathBuf::from(value));
+        } else if arg == "--emit-asm" {
+            emit_assembly_only = true;
+        } else if arg == "--interpret" {
+            interpret = true;
+        } else if arg == "--overflow-checks" {
+            options.overflow_checks = true;
+        } else {
+            file_names.push(arg.clone());
+        }
+    }
+
+    // The interpreter always lets integer arithmetic wrap around, so it would silently ignore the flag
+    if interpret && options.overflow_checks {
+        return Err(Diagnostic::new(
+            ErrorKind::Usage,
+            "--overflow-checks can't be used along with --interpret",
+        )
+        .with_note("the interpreter always lets integer arithmetic wrap around")
+        .render("", "")
+        .into());
+    }
+
+    let input_file_name = if !file_names.is_empty() {
+        file_names[0].clone()
     } else {
-        panic!("No source file was provided. Pass in a file path as the first argument to the program.")
+        return Err(Diagnostic::new(
+            ErrorKind::Usage,
+            "No source file was provided. Pass in a file path as the first argument to the program.",
+        )
+        .render("", "")
+        .into());
     };
 
-    let output_file_name = if args.len() > 2 {
-        args[2].clone()
+    let output_file_name = if file_names.len() > 1 {
+        file_names[1].clone()
     } else {
         // If no output file name was provided, use the same name as the source file, but strip off the
-        // file extension (if it exists) (TODO)
-        args[1].clone()
+        // file extension (if it exists) - otherwise the executable would overwrite the source file on Linux
+        std::path::Path::new(&file_names[0])
+            .with_extension("")
+            .to_string_lossy()
+            .to_string()
     };
 
-    let source_text = std::fs::read_to_string(input_file_name.clone())
-        .expect(format!("Could not read source file {}", input_file_name).as_str());
+    let source_text = std::fs::read_to_string(input_file_name.clone()).map_err(|e| {
+        Diagnostic::new(
+            ErrorKind::Io,
+            format!("Could not read source file {input_file_name} ({e})"),
+        )
+        .render("", "")
+    })?;
 
-    match medusa_lang::compile_from_text(&source_text, &output_file_name) {
-        Ok(()) => {}
-        Err(e) => {
-            panic!("Compile error: {}", e);
-        }
+    // From here on, errors can point into the source file
+    run_source(
+        &source_text,
+        &output_file_name,
+        target,
+        assembler,
+        linker,
+        libraries,
+        emit_assembly_only,
+        interpret,
+        options,
+    )
+    .map_err(|e| Failure {
+        diagnostics: e.render(&input_file_name, &source_text),
+        exit_code: e.exit_code(),
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_source(
+    source_text: &str,
+    output_file_name: &str,
+    target: Target,
+    assembler: Option<PathBuf>,
+    linker: Option<PathBuf>,
+    libraries: Vec<PathBuf>,
+    emit_assembly_only: bool,
+    interpret: bool,
+    options: CompileOptions,
+) -> Result<(), CompileError> {
+    // Run the program straight away instead of compiling it - no files are produced
+    if interpret {
+        let stdin = std::io::stdin();
+        let stdout = std::io::stdout();
+
+        return medusa_lang::interpret_from_text(
+            source_text,
+            &mut stdin.lock(),
+            &mut stdout.lock(),
+        );
+    }
+
+    // Stop after writing the .asm file - no assembler or linker is needed for this
+    if emit_assembly_only {
+        let assembly_source =
+            medusa_lang::compile_to_assembly_with_options(source_text, target, options)?;
+
+        return std::fs::write(format!("{}.asm", output_file_name), assembly_source).map_err(|e| {
+            CompileError::from(Diagnostic::new(
+                ErrorKind::Io,
+                format!("Could not write assembly source file ({e})"),
+            ))
+        });
+    }
+
+    // Any tools that weren't configured on the commandline are searched for on the PATH. If libraries were
+    // passed in, they replace the default system libraries for the target entirely.
+    let toolchain = discover_toolchain(target, assembler, linker, libraries)?;
+
+    medusa_lang::compile_with_toolchain(source_text, output_file_name, &toolchain, options)
+}
+
+fn discover_toolchain(
+    target: Target,
+    assembler: Option<PathBuf>,
+    linker: Option<PathBuf>,
+    libraries: Vec<PathBuf>,
+) -> Result<Toolchain, CompileError> {
+    let assembler = match assembler {
+        Some(assembler) => assembler,
+        None => Toolchain::find_assembler(target)?,
+    };
+
+    let linker = match linker {
+        Some(linker) => linker,
+        None => Toolchain::find_linker(target)?,
     };
+
+    let libraries = if libraries.is_empty() {
+        Toolchain::find_libraries(target)?
+    } else {
+        libraries
+    };
+
+    Ok(Toolchain::new(target, assembler, linker, libraries))
 }
@@ -0,0 +1,262 @@
+// The external tools that turn the generated assembly into an executable. Everything that depends on the
+// host operating system (PATH lookups, the bundled Windows binaries, and the Windows SDK registry keys) is
+// kept in this file so that the rest of the compiler builds the same way everywhere.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use crate::{CompileError, Diagnostic, ErrorKind, Target};
+
+/// The assembler, linker, and system libraries used to build executables for a target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toolchain {
+    pub target: Target,
+    /// Path to NASM.
+    pub assembler: PathBuf,
+    /// Path to the linker - ld.lld for Windows, ld for Linux.
+    pub linker: PathBuf,
+    /// Libraries passed to the linker after the object file, ex. kernel32.lib on Windows.
+    pub libraries: Vec<PathBuf>,
+}
+
+impl Toolchain {
+    /// Creates a toolchain out of explicitly configured tools, without searching for anything.
+    pub fn new(
+        target: Target,
+        assembler: PathBuf,
+        linker: PathBuf,
+        libraries: Vec<PathBuf>,
+    ) -> Toolchain {
+        Toolchain {
+            target,
+            assembler,
+            linker,
+            libraries,
+        }
+    }
+
+    /// Searches the host for every tool and library needed to build executables for the target.
+    pub fn discover(target: Target) -> Result<Toolchain, CompileError> {
+        Ok(Toolchain {
+            target,
+            assembler: Toolchain::find_assembler(target)?,
+            linker: Toolchain::find_linker(target)?,
+            libraries: Toolchain::find_libraries(target)?,
+        })
+    }
+
+    /// Looks for NASM on the PATH, falling back to the copy bundled in ./windows/ for Windows hosts.
+    pub fn find_assembler(_target: Target) -> Result<PathBuf, CompileError> {
+        find_tool(&["nasm"], "windows/nasm.exe").ok_or_else(|| {
+            CompileError::from(Diagnostic::new(
+                ErrorKind::Toolchain,
+                "Could not find the assembler. Is NASM installed and on your PATH?",
+            ))
+        })
+    }
+
+    /// Looks for the linker that matches the target on the PATH.
+    pub fn find_linker(target: Target) -> Result<PathBuf, CompileError> {
+        match target {
+            Target::Windows => find_tool(&["ld.lld"], "windows/ld.lld.exe").ok_or_else(|| {
+                CompileError::from(Diagnostic::new(
+                    ErrorKind::Toolchain,
+                    "Could not find ld.lld. Is LLVM installed and on your PATH?",
+                ))
+            }),
+            Target::Linux => find_tool(&["ld", "ld.lld"], "").ok_or_else(|| {
+                CompileError::from(Diagnostic::new(
+                    ErrorKind::Toolchain,
+                    "Could not find the linker. Is ld (from binutils) installed and on your PATH?",
+                ))
+            }),
+        }
+    }
+
+    /// Finds the system libraries that programs for the target are linked against. Linux programs talk to
+    /// the kernel directly, so they don't need any.
+    pub fn find_libraries(target: Target) -> Result<Vec<PathBuf>, CompileError> {
+        match target {
+            Target::Windows => Ok(vec![
+                PathBuf::from("C:/Windows/System32/user32.dll"),
+                windows_sdk::find_kernel32()?,
+            ]),
+            Target::Linux => Ok(Vec::new()),
+        }
+    }
+
+    /// Assembles the .asm file into an object file, producing a listing file next to it.
+    pub(crate) fn assemble(&self, output_file_name: &str) -> Result<(), CompileError> {
+        let format = match self.target {
+            Target::Windows => "win64",
+            Target::Linux => "elf64",
+        };
+
+        let assembler_output = run_tool(
+            "Assembler",
+            Command::new(&self.assembler).args([
+                "-f",
+                format,
+                format!("{}.asm", output_file_name).as_str(),
+                "-o",
+                self.target.object_file_name(output_file_name).as_str(),
+                "-l",
+                format!("{}.lst", output_file_name).as_str(),
+            ]),
+        )?;
+
+        check_tool_output("Assembler", assembler_output)
+    }
+
+    /// Links the object file and the system libraries into an executable.
+    pub(crate) fn link(&self, output_file_name: &str) -> Result<(), CompileError> {
+        let linker_output = run_tool(
+            "Linker",
+            Command::new(&self.linker)
+                .arg(self.target.object_file_name(output_file_name))
+                .arg("-o")
+                .arg(self.target.executable_file_name(output_file_name))
+                .args(&self.libraries),
+        )?;
+
+        check_tool_output("Linker", linker_output)
+    }
+}
+
+/// Searches the PATH for the first of the given tools, then falls back to the bundled copy (if there is one).
+fn find_tool(names: &[&str], bundled: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+
+    for name in names {
+        for folder in std::env::split_paths(&path) {
+            let candidate = folder.join(format!("{name}{}", std::env::consts::EXE_SUFFIX));
+
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+
+    if cfg!(windows) && !bundled.is_empty() && Path::new(bundled).is_file() {
+        return Some(PathBuf::from(bundled));
+    }
+
+    None
+}
+
+/// Prints the result of running the assembler or linker, or turns it into an error if the tool failed.
+fn check_tool_output(tool_name: &str, output: Output) -> Result<(), CompileError> {
+    match output.status.code() {
+        Some(0) => {
+            let error_text = String::from_utf8_lossy(&output.stderr);
+
+            if !error_text.is_empty() {
+                println!("{tool_name} succeeded with warnings: {}", error_text);
+            } else {
+                println!("{tool_name} succeeded");
+            }
+
+            Ok(())
+        }
+        _ => Err(CompileError::from(Diagnostic::new(
+            ErrorKind::Toolchain,
+            format!(
+                "{tool_name} failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ),
+        ))),
+    }
+}
+
+/// Runs a tool that the compiler depends on, turning a missing executable into a compile error.
+fn run_tool(tool_name: &str, command: &mut Command) -> Result<Output, CompileError> {
+    command.output().map_err(|e| {
+        CompileError::from(Diagnostic::new(
+            ErrorKind::Toolchain,
+            format!("Could not run the {tool_name} ({e}). Is it installed?"),
+        ))
+    })
+}
+
+#[cfg(windows)]
+mod windows_sdk {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    use crate::{CompileError, Diagnostic, ErrorKind};
+
+    /// Digs through the registry keys to find the exact folder path to the Windows SDK - this is
+    /// necessary to link against kernel32.lib
+    pub fn find_kernel32() -> Result<PathBuf, CompileError> {
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        let sdk = match hklm
+            .open_subkey("SOFTWARE\\WOW6432Node\\Microsoft\\Microsoft SDKs\\Windows\\v10.0")
+        {
+            Ok(v) => v,
+            Err(_e) => {
+                return Err(CompileError::from(Diagnostic::new(ErrorKind::Toolchain, "Could not locate registry key for Windows SDK version. Is the Windows SDK installed?")));
+            }
+        };
+
+        let sdk_installation_folder: String = match sdk.get_value("InstallationFolder") {
+            Ok(v) => v,
+            Err(_e) => {
+                return Err(CompileError::from(Diagnostic::new(ErrorKind::Toolchain, "Could not locate registry key for Windows SDK installation folder. Is the Windows SDK installed?")));
+            }
+        };
+
+        let sdk_installation_version: String = match sdk.get_value("ProductVersion") {
+            Ok(v) => v,
+            Err(_e) => {
+                return Err(CompileError::from(Diagnostic::new(ErrorKind::Toolchain, "Could not locate registry key for Windows SDK installation version. Is the Windows SDK installed?")));
+            }
+        };
+
+        // We need to find the exact path to the Windows SDK - this is made difficult because the product version
+        // does not line up directly with the actual folder name, ex. the product version on my machine is
+        // 10.0.22000 while the folder path is 10.0.22000.0.
+        let paths = fs::read_dir(format!("{sdk_installation_folder}Lib\\")).unwrap();
+
+        let mut kernel32_path: Option<PathBuf> = None;
+
+        for path in paths {
+            // Strip the path down to just the folder number, ex. 10.0.22000.0
+            let path = path.unwrap().path();
+            let path = path
+                .strip_prefix(format!("{sdk_installation_folder}Lib\\"))
+                .unwrap();
+
+            // Compare it to the product version and see if everything but the last digits line up
+
+            let folder_number = path.to_str().unwrap();
+
+            // Trim the folder number so that it's the same length as the product version before comparing
+            if folder_number[..sdk_installation_version.len()] == sdk_installation_version {
+                kernel32_path = Some(PathBuf::from(format!(
+                    "{sdk_installation_folder}Lib\\{folder_number}\\um\\x64\\kernel32.lib"
+                )));
+            }
+        }
+
+        match kernel32_path {
+            Some(path) => Ok(path),
+            None => Err(CompileError::from(Diagnostic::new(ErrorKind::Toolchain, format!("Could not locate the Windows SDK folder at {sdk_installation_folder}Lib\\{sdk_installation_version}. Is the Windows SDK installed correctly?")))),
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod windows_sdk {
+    use std::path::PathBuf;
+
+    use crate::{CompileError, Diagnostic, ErrorKind};
+
+    /// The Windows SDK can only be found through the registry, so kernel32.lib has to be passed in
+    /// explicitly when building Windows executables on any other host.
+    pub fn find_kernel32() -> Result<PathBuf, CompileError> {
+        Err(CompileError::from(Diagnostic::new(ErrorKind::Toolchain, "Could not locate kernel32.lib - the Windows SDK can only be discovered on Windows. Pass the path to kernel32.lib in with --library")))
+    }
+}
@@ -0,0 +1,160 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn array_1() {
+    let program = r#"
+int[5] squares;
+for (int i = 0; i < #squares; i = i + 1) {
+    squares[i] = i * i;
+};
+for (int i = 0; i < #squares; i = i + 1) {
+    squares[i] -> @;
+};
+"#;
+
+    let expected_output = "014916";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn array_2() {
+    let program = r#"
+// Elements start out empty, and strings are copied into their own element
+string[3] words;
+string word = "hello";
+words[1] = word;
+word = "changed";
+words[0] .. "|" .. words[1] .. "|" .. words[2] -> @;
+"#;
+
+    let expected_output = "|hello|";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn array_3() {
+    let program = r#"
+// The length can be worked out while the program is running
+int count = 2 + 2;
+float[count] halves;
+bool[count] even;
+for (int i = 0; i < #halves; i = i + 1) {
+    halves[i] = (float) i / 2.0;
+    even[i] = i % 2 == 0;
+};
+halves[3] -> @;
+even[2] -> @;
+even[3] -> @;
+#even -> @;
+"#;
+
+    let expected_output = "1.500000truefalse4";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn array_4() {
+    let program = r#"
+// Arrays declared in a function belong to that call
+int sum(int n) {
+    int[n] values;
+    int total = 0;
+    for (int i = 0; i < n; i = i + 1) {
+        values[i] = i + 1;
+    };
+    for (int i = 0; i < #values; i = i + 1) {
+        total = total + values[i];
+    };
+    return total;
+};
+sum(4) -> @;
+sum(10) -> @;
+"#;
+
+    let expected_output = "1055";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn array_5() {
+    let program = r#"
+// Every time a declaration runs, the old array (and its strings) are freed and handed out again, so a
+// redeclared array starts out empty. The same goes for the arrays of a function when it returns.
+string label(int n) {
+    string[2] parts;
+    parts[0] = (string) n;
+    parts[1] = "!";
+    return parts[0] .. parts[1];
+};
+string result;
+for (int i = 0; i < 3; i = i + 1) {
+    string[2] words;
+    result = result .. words[0] .. words[1] .. label(i);
+    words[0] = "a" .. (string) i;
+    words[1] = "b";
+};
+result -> @;
+"#;
+
+    let expected_output = "0!1!2!";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn index_out_of_bounds() {
+    let program = r#"
+int[3] values;
+values[2] = 7;
+values[2] -> @;
+values[3] -> @;
+"done" -> @;
+"#;
+
+    let (exit_code, output) =
+        common::compile_and_get_exit_code_and_stripped_output(program, function_name!(), "");
+
+    assert_eq!(exit_code, 2);
+    assert_eq!(
+        output,
+        "7Runtime error on line 5: Array index out of bounds5 | values[3] -> @;"
+    );
+}
+
+#[test]
+fn negative_index() {
+    let program = r#"
+int[3] values;
+values[0 - 1] = 7;
+"#;
+
+    let (exit_code, output) =
+        common::compile_and_get_exit_code_and_stripped_output(program, function_name!(), "");
+
+    assert_eq!(exit_code, 2);
+    assert_eq!(
+        output,
+        "Runtime error on line 3: Array index out of bounds3 | values[0 - 1] = 7;"
+    );
+}
@@ -0,0 +1,102 @@
+use medusa_lang::Target;
+
+#[test]
+fn linux_entry_point() {
+    let program = r#"
+"Hello, world!" -> @;
+"#;
+
+    let assembly = medusa_lang::compile_to_assembly_for_target(program, Target::Linux).unwrap();
+
+    assert!(assembly.contains("global _start"));
+    assert!(!assembly.contains("mainCRTStartup"));
+}
+
+#[test]
+fn windows_entry_point() {
+    let program = r#"
+"Hello, world!" -> @;
+"#;
+
+    let assembly = medusa_lang::compile_to_assembly_for_target(program, Target::Windows).unwrap();
+
+    assert!(assembly.contains("global mainCRTStartup"));
+    assert!(!assembly.contains("_start:"));
+}
+
+#[test]
+fn deterministic_output() {
+    let program = r#"
+int c = 3;
+float b = 2.5;
+string a = "one";
+int d <- @;
+a -> @;
+"#;
+
+    let first = medusa_lang::compile_to_assembly_for_target(program, Target::Linux).unwrap();
+    let second = medusa_lang::compile_to_assembly_for_target(program, Target::Linux).unwrap();
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn parse_error() {
+    let program = r#"
+5 -> ;
+"#;
+
+    assert!(medusa_lang::compile_to_assembly_for_target(program, Target::Linux).is_err());
+}
+
+#[test]
+fn temporary_strings_are_freed() {
+    let temporary = r#"
+string s = "a" .. "b";
+"#;
+
+    let literal = r#"
+string s = "a";
+"#;
+
+    let free_after_assign = "call medusa_assign_string\nmov rdx, r8\ncall medusa_free_string";
+
+    let assembly = medusa_lang::compile_to_assembly_for_target(temporary, Target::Linux).unwrap();
+    assert!(assembly.contains(free_after_assign));
+
+    // Literals live in the data section, so they're never freed
+    let assembly = medusa_lang::compile_to_assembly_for_target(literal, Target::Linux).unwrap();
+    assert!(!assembly.contains(free_after_assign));
+}
+
+#[test]
+fn arrays_are_freed() {
+    let program = r#"
+int count(int n) {
+    int[n] values;
+    return #values;
+};
+for (int i = 0; i < 3; i = i + 1) {
+    string[2] words;
+};
+"#;
+
+    let assembly = medusa_lang::compile_to_assembly_for_target(program, Target::Linux).unwrap();
+
+    // The old array is freed before the declaration allocates a new one
+    assert!(assembly.contains("]\ncall medusa_free_string_array\n\n; Allocate the array"));
+
+    // A function's arrays are freed when it returns
+    assert!(assembly.contains("mov rcx, [rbp - 16]\ncall medusa_free_array\n"));
+}
+
+#[test]
+fn string_literals_are_byte_lists() {
+    let program = r#"
+"a`;\"" -> @;
+"#;
+
+    let assembly = medusa_lang::compile_to_assembly_for_target(program, Target::Linux).unwrap();
+
+    assert!(assembly.contains("dq 4, 4\nstring_0 db 97, 96, 59, 34, 0\n"));
+}
@@ -0,0 +1,193 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn bool_literals() {
+    let program = r#"
+bool yes = true;
+bool no = false;
+yes -> @;
+no -> @;
+"#;
+
+    let expected_output = "truefalse";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn bool_from_comparison() {
+    let program = r#"
+int x = 5;
+bool big = x > 3;
+bool small = x < 3;
+big -> @;
+small -> @;
+"#;
+
+    let expected_output = "truefalse";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn bool_variable_as_condition() {
+    let program = r#"
+bool running = true;
+int count = 0;
+while (running) {
+    count = count + 1;
+    if (count == 3) {
+        running = false;
+    };
+};
+count -> @;
+"#;
+
+    let expected_output = "3";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn and_1() {
+    let program = r#"
+for (int i = 0; i < 4; i = i + 1) {
+    bool a = i > 1;
+    bool b = i % 2 == 1;
+    a && b -> @;
+    if (a && b) {
+        "yes" -> @;
+    };
+};
+"#;
+
+    let expected_output = "falsefalsefalsetrueyes";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn or_1() {
+    let program = r#"
+for (int i = 0; i < 4; i = i + 1) {
+    bool a = i > 1;
+    bool b = i % 2 == 1;
+    a || b -> @;
+    if (a || b) {
+        "yes" -> @;
+    };
+};
+"#;
+
+    let expected_output = "falsetrueyestrueyestrueyes";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn not_1() {
+    let program = r#"
+bool x = false;
+!x -> @;
+!!x -> @;
+if (!(1 > 2)) {
+    "yes" -> @;
+};
+"#;
+
+    let expected_output = "truefalseyes";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn precedence() {
+    let program = r#"
+// && binds tighter than ||, and comparisons bind tighter than both
+true || false && false -> @;
+(true || false) && false -> @;
+1 + 1 == 2 && 2 * 3 > 5 -> @;
+!false && false -> @;
+"#;
+
+    let expected_output = "truefalsetruefalse";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn short_circuit_and() {
+    let program = r#"
+int x = 0;
+if (x != 0 && 10 / x > 1) {
+    "divided" -> @;
+} else {
+    "skipped" -> @;
+};
+"#;
+
+    // The division would crash the program if it ran
+    let expected_output = "skipped";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn short_circuit_or() {
+    let program = r#"
+int x = 0;
+bool safe = x == 0 || 10 / x > 1;
+safe -> @;
+"#;
+
+    let expected_output = "true";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn bool_equality() {
+    let program = r#"
+bool a = 1 < 2;
+bool b = 3 < 4;
+a == b -> @;
+a != b -> @;
+"bool is " .. (string) (a == false) -> @;
+"#;
+
+    let expected_output = "truefalsebool is false";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
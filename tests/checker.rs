@@ -0,0 +1,314 @@
+use medusa_lang::ast::{
+    BinaryOperator, DeclarationValue, Expression, ExpressionKind, Statement, VariableDataType,
+};
+use medusa_lang::ErrorKind;
+
+#[test]
+fn resolved_datatypes() {
+    let program = r#"
+float x = (float)2 * 1.5;
+"#;
+
+    let program = medusa_lang::check_from_text(program).unwrap();
+
+    let Statement::Declaration(declaration) = &program.statements[0] else {
+        panic!("Expected a declaration");
+    };
+
+    assert_eq!(declaration.datatype, VariableDataType::FLOAT);
+
+    let DeclarationValue::Assignment(assignment) = &declaration.value else {
+        panic!("Expected an assignment");
+    };
+
+    let ExpressionKind::Binary {
+        operator,
+        left,
+        right,
+    } = &assignment.value.kind
+    else {
+        panic!("Expected a binary expression");
+    };
+
+    assert_eq!(*operator, BinaryOperator::Multiply);
+    assert_eq!(assignment.value.datatype, VariableDataType::FLOAT);
+    assert!(matches!(left.kind, ExpressionKind::Cast(_)));
+    assert_eq!(right.kind, ExpressionKind::Float(1.5));
+}
+
+#[test]
+fn expression_spans() {
+    let source = "int x = 1 + 23;";
+
+    let program = medusa_lang::check_from_text(source).unwrap();
+
+    let Statement::Declaration(declaration) = &program.statements[0] else {
+        panic!("Expected a declaration");
+    };
+
+    let DeclarationValue::Assignment(assignment) = &declaration.value else {
+        panic!("Expected an assignment");
+    };
+
+    let span = assignment.value.span;
+    assert_eq!(&source[span.start..span.end], "1 + 23");
+}
+
+#[test]
+fn variables_are_sorted() {
+    let program = r#"
+string b;
+int a;
+float c;
+"#;
+
+    let program = medusa_lang::check_from_text(program).unwrap();
+
+    assert_eq!(
+        program.variables,
+        vec![
+            ("a".to_string(), VariableDataType::INT),
+            ("b".to_string(), VariableDataType::STRING),
+            ("c".to_string(), VariableDataType::FLOAT),
+        ]
+    );
+}
+
+#[test]
+fn scoped_variables_are_renamed() {
+    let program = r#"
+int x = 1;
+if (x > 0) {
+    float x = 2.5;
+    x -> @;
+};
+"#;
+
+    let program = medusa_lang::check_from_text(program).unwrap();
+
+    assert_eq!(
+        program.variables,
+        vec![
+            ("x".to_string(), VariableDataType::INT),
+            ("x_1".to_string(), VariableDataType::FLOAT),
+        ]
+    );
+
+    let Statement::If(if_statement) = &program.statements[1] else {
+        panic!("Expected an if statement");
+    };
+
+    let Statement::Output(output) = &if_statement.body[1] else {
+        panic!("Expected an output statement");
+    };
+
+    assert_eq!(
+        output.value.kind,
+        ExpressionKind::Variable("x_1".to_string())
+    );
+}
+
+#[test]
+fn mismatched_datatypes() {
+    let program = r#"
+int x = 5;
+x = "five";
+"#;
+
+    assert!(medusa_lang::check_from_text(program).is_err());
+}
+
+#[test]
+fn undeclared_variable_error() {
+    let source = "int x = 5;\nx + y -> @;";
+
+    let error = medusa_lang::check_from_text(source).unwrap_err();
+
+    assert_eq!(error.diagnostics[0].kind, ErrorKind::UndeclaredVariable);
+
+    let span = error.diagnostics[0].span.unwrap();
+    assert_eq!(&source[span.start..span.end], "y");
+}
+
+#[test]
+fn syntax_error() {
+    let source = "5 -> ;";
+
+    let error = medusa_lang::check_from_text(source).unwrap_err();
+
+    assert_eq!(error.diagnostics[0].kind, ErrorKind::Syntax);
+    assert!(error.diagnostics[0].span.is_some());
+}
+
+#[test]
+fn unary_minus() {
+    let program = medusa_lang::check_from_text("int x = 1;\nint y = x-1;\nint z = -x;\n").unwrap();
+
+    let Statement::Declaration(declaration) = &program.statements[1] else {
+        panic!("Expected a declaration");
+    };
+
+    let DeclarationValue::Assignment(assignment) = &declaration.value else {
+        panic!("Expected an assignment");
+    };
+
+    let ExpressionKind::Binary {
+        operator, right, ..
+    } = &assignment.value.kind
+    else {
+        panic!("Expected a binary expression");
+    };
+
+    assert_eq!(*operator, BinaryOperator::Subtract);
+    assert_eq!(right.kind, ExpressionKind::Int(1));
+
+    let Statement::Declaration(declaration) = &program.statements[2] else {
+        panic!("Expected a declaration");
+    };
+
+    let DeclarationValue::Assignment(assignment) = &declaration.value else {
+        panic!("Expected an assignment");
+    };
+
+    assert!(matches!(assignment.value.kind, ExpressionKind::Negate(_)));
+
+    let error = medusa_lang::check_from_text("bool b = -true;\n").unwrap_err();
+    assert_eq!(error.diagnostics[0].kind, ErrorKind::TypeMismatch);
+}
+
+#[test]
+fn smallest_int_literal() {
+    let program = medusa_lang::check_from_text("int x = -9223372036854775808;\n").unwrap();
+
+    let Statement::Declaration(declaration) = &program.statements[0] else {
+        panic!("Expected a declaration");
+    };
+
+    let DeclarationValue::Assignment(assignment) = &declaration.value else {
+        panic!("Expected an assignment");
+    };
+
+    assert_eq!(assignment.value.kind, ExpressionKind::Int(i64::MIN));
+
+    // Only a minus sign straight in front of a literal is folded
+    let program = medusa_lang::check_from_text("int x = -(-9223372036854775808);\n").unwrap();
+
+    let Statement::Declaration(declaration) = &program.statements[0] else {
+        panic!("Expected a declaration");
+    };
+
+    let DeclarationValue::Assignment(assignment) = &declaration.value else {
+        panic!("Expected an assignment");
+    };
+
+    assert!(matches!(assignment.value.kind, ExpressionKind::Negate(_)));
+
+    for source in [
+        "int x = 9223372036854775808;\n",
+        "int x = -(9223372036854775808);\n",
+        "int x = -9223372036854775809;\n",
+    ] {
+        let error = medusa_lang::check_from_text(source).unwrap_err();
+        assert_eq!(error.diagnostics[0].kind, ErrorKind::InvalidLiteral);
+    }
+}
+
+/// Writes out an expression with every operation in parentheses, to show how it was grouped.
+fn parenthesize(expression: &Expression) -> String {
+    match &expression.kind {
+        ExpressionKind::Int(value) => value.to_string(),
+        ExpressionKind::Float(value) => format!("{value:?}"),
+        ExpressionKind::String(value) => format!("\"{}\"", String::from_utf8_lossy(value)),
+        ExpressionKind::Bool(value) => value.to_string(),
+        // The checker gives every variable a unique name, so strip that back off
+        ExpressionKind::Variable(name) => name.split('_').next().unwrap().to_string(),
+        ExpressionKind::Binary {
+            operator,
+            left,
+            right,
+        } => format!(
+            "({} {} {})",
+            parenthesize(left),
+            operator.as_str(),
+            parenthesize(right)
+        ),
+        ExpressionKind::Comparison {
+            operator,
+            left,
+            right,
+        } => format!(
+            "({} {} {})",
+            parenthesize(left),
+            operator.as_str(),
+            parenthesize(right)
+        ),
+        ExpressionKind::Logical {
+            operator,
+            left,
+            right,
+        } => format!(
+            "({} {} {})",
+            parenthesize(left),
+            operator.as_str(),
+            parenthesize(right)
+        ),
+        ExpressionKind::Not(operand) => format!("(!{})", parenthesize(operand)),
+        ExpressionKind::Negate(operand) => format!("(-{})", parenthesize(operand)),
+        ExpressionKind::Cast(operand) => {
+            format!("(({}){})", expression.datatype, parenthesize(operand))
+        }
+        kind => panic!("Unexpected expression {kind:?}"),
+    }
+}
+
+#[test]
+fn operator_precedence() {
+    let declarations = "int a = 1;\nint b = 2;\nint c = 3;\nfloat f = 1.5;\nbool p = true;\nbool q = false;\nstring s = \"s\";\n";
+
+    let cases = [
+        // Each level against the one above it
+        ("p || q && p", "(p || (q && p))"),
+        ("p && a < b", "(p && (a < b))"),
+        ("s .. s == s", "((s .. s) == s)"),
+        ("a + b < c", "((a + b) < c)"),
+        ("a < b + c", "(a < (b + c))"),
+        ("a + b * c", "(a + (b * c))"),
+        ("a * b + c", "((a * b) + c)"),
+        ("a * b ^ c", "(a * (b ^ c))"),
+        ("a ^ b * c", "((a ^ b) * c)"),
+        ("-a ^ b", "((-a) ^ b)"),
+        ("(float)a ^ f", "(((float)a) ^ f)"),
+        ("!p && q", "((!p) && q)"),
+        ("(string)a .. s", "(((string)a) .. s)"),
+        // Operators on the same level
+        ("a - b + c", "((a - b) + c)"),
+        ("a / b * c", "((a / b) * c)"),
+        ("a % b / c", "((a % b) / c)"),
+        ("s .. s .. s", "((s .. s) .. s)"),
+        ("p || q || p", "((p || q) || p)"),
+        ("p && q && p", "((p && q) && p)"),
+        // Power is the only right-associative operator
+        ("a ^ b ^ c", "(a ^ (b ^ c))"),
+        // Prefix operators
+        ("a - -b", "(a - (-b))"),
+        ("a-1", "(a - 1)"),
+        ("-(a + b)", "(-(a + b))"),
+        ("+a * -b", "(a * (-b))"),
+        ("- -a", "(-(-a))"),
+        ("-1 - 2", "(-1 - 2)"),
+        ("!!p", "(!(!p))"),
+        ("(a + b) * c", "((a + b) * c)"),
+    ];
+
+    for (expression, expected) in cases {
+        let source = format!("{declarations}{expression} -> @;\n");
+
+        let program = medusa_lang::check_from_text(&source).unwrap();
+
+        let Statement::Output(output) = program.statements.last().unwrap() else {
+            panic!("Expected an output statement");
+        };
+
+        assert_eq!(parenthesize(&output.value), expected, "{expression}");
+    }
+}
@@ -0,0 +1,57 @@
+#[test]
+fn compile_error_exit_code() {
+    let source_file_name = "./tests/command_line_compile_error_exit_code.med";
+
+    std::fs::write(source_file_name, "x -> @;\n").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_medusa_lang"))
+        .arg(source_file_name)
+        .arg("--emit-asm")
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(source_file_name).unwrap();
+
+    // The error is reported on stderr rather than as a panic
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("Variable used but not declared"));
+    assert!(!stderr.contains("panicked"));
+}
+
+#[test]
+fn interpreter_runtime_error_exit_code() {
+    let source_file_name = "./tests/command_line_interpreter_runtime_error_exit_code.med";
+
+    std::fs::write(source_file_name, "int x = 0;\n5 / x -> @;\n").unwrap();
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_medusa_lang"))
+        .arg(source_file_name)
+        .arg("--interpret")
+        .output()
+        .unwrap();
+
+    std::fs::remove_file(source_file_name).unwrap();
+
+    // The interpreter exits with the same code as the compiled program would
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(4));
+    assert!(stderr.contains("Division by zero"));
+}
+
+#[test]
+fn interpreter_rejects_overflow_checks() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_medusa_lang"))
+        .arg("./tests/command_line_interpreter_rejects_overflow_checks.med")
+        .arg("--interpret")
+        .arg("--overflow-checks")
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("--overflow-checks can't be used along with --interpret"));
+}
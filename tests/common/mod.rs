@@ -1,5 +1,65 @@
 // Retrieve all of the output from a compiled medusa program
+#[allow(dead_code)]
 pub fn compile_and_get_output(source_text: &str, test_name: &str) -> String {
+    compile_and_get_output_with_input(source_text, test_name, "")
+}
+
+// Retrieve all of the output from a compiled medusa program, typing in the given input
+#[allow(dead_code)]
+pub fn compile_and_get_output_with_input(
+    source_text: &str,
+    test_name: &str,
+    input: &str,
+) -> String {
+    match compile_and_run(source_text, test_name, input) {
+        (0, output) => output,
+        _ => {
+            panic!("Failed to execute test")
+        }
+    }
+}
+
+// Retrieve the exit code and the stripped output from a compiled medusa program, typing in the given input,
+//  for programs that are expected to fail while they're running
+#[allow(dead_code)]
+pub fn compile_and_get_exit_code_and_stripped_output(
+    source_text: &str,
+    test_name: &str,
+    input: &str,
+) -> (i32, String) {
+    let (exit_code, output) = compile_and_run(source_text, test_name, input);
+
+    (exit_code, strip_output(output))
+}
+
+// Same as compile_and_get_exit_code_and_stripped_output, but compiling the program with the given options
+#[allow(dead_code)]
+pub fn compile_and_get_exit_code_and_stripped_output_with_options(
+    source_text: &str,
+    test_name: &str,
+    input: &str,
+    options: medusa_lang::CompileOptions,
+) -> (i32, String) {
+    let (exit_code, output) = compile_and_run_with_options(source_text, test_name, input, options);
+
+    (exit_code, strip_output(output))
+}
+
+fn compile_and_run(source_text: &str, test_name: &str, input: &str) -> (i32, String) {
+    compile_and_run_with_options(
+        source_text,
+        test_name,
+        input,
+        medusa_lang::CompileOptions::default(),
+    )
+}
+
+fn compile_and_run_with_options(
+    source_text: &str,
+    test_name: &str,
+    input: &str,
+    options: medusa_lang::CompileOptions,
+) -> (i32, String) {
     // The test name is in this kind of format: conversion_operations::string_to_int_3
     // Which isn't good, because Windows (and probably all other OS') don't like colons in file names,
     // So let's remove the colons and replace them with underscores
@@ -10,33 +70,78 @@ pub fn compile_and_get_output(source_text: &str, test_name: &str) -> String {
     // Make sure the tests are stored in the right directory
     let test_name: String = "./tests/".to_string() + test_name.as_str();
 
-    medusa_lang::compile_from_text(source_text, &test_name).unwrap();
+    // The executable and object file names depend on the platform, ex. test.exe on Windows and test on Linux
+    let target = medusa_lang::Target::host();
+
+    let toolchain = medusa_lang::Toolchain::discover(target).unwrap();
+    medusa_lang::compile_with_toolchain(source_text, &test_name, &toolchain, options).unwrap();
 
-    // File is available at test.exe
-    let output = std::process::Command::new(format!("./{test_name}.exe"))
-        .output()
+    let executable_file_name = target.executable_file_name(&test_name);
+    let object_file_name = target.object_file_name(&test_name);
+
+    let mut child = std::process::Command::new(format!("./{executable_file_name}"))
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
         .unwrap();
 
-    // Destroy the .exe, .asm, .obj, and .lst now that we're done with them
-    std::fs::remove_file(format!("{test_name}.exe")).unwrap();
+    // Dropping stdin once the input has been written closes it, so the program sees the end of the input
+    std::io::Write::write_all(&mut child.stdin.take().unwrap(), input.as_bytes()).unwrap();
+
+    let output = child.wait_with_output().unwrap();
+
+    // Destroy the executable, .asm, object file, and .lst now that we're done with them
+    std::fs::remove_file(executable_file_name).unwrap();
     std::fs::remove_file(format!("{test_name}.asm")).unwrap();
-    std::fs::remove_file(format!("{test_name}.obj")).unwrap();
+    std::fs::remove_file(object_file_name).unwrap();
     std::fs::remove_file(format!("{test_name}.lst")).unwrap();
 
-    match output.status.code().unwrap() {
-        0 => String::from_utf8(output.stdout).unwrap(),
-        _ => {
-            panic!("Failed to execute test")
-        }
-    }
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
 }
 
 // Retrieve only the program-specific output from a compiled medusa program, with all headers
 //  and footers stripped (i.e. Medusa 1.0 and Program ended are removed) and all formatting
 //  characters stripped (i.e. \n and \0)
+#[allow(dead_code)]
 pub fn compile_and_get_stripped_output(source_text: &str, test_name: &str) -> String {
-    let output = compile_and_get_output(source_text, test_name);
+    strip_output(compile_and_get_output(source_text, test_name))
+}
+
+// Same as compile_and_get_stripped_output, but typing in the given input
+#[allow(dead_code)]
+pub fn compile_and_get_stripped_output_with_input(
+    source_text: &str,
+    test_name: &str,
+    input: &str,
+) -> String {
+    strip_output(compile_and_get_output_with_input(
+        source_text,
+        test_name,
+        input,
+    ))
+}
+
+// Retrieve all of the output from a medusa program run by the interpreter, typing in the given input
+#[allow(dead_code)]
+pub fn interpret_and_get_output(source_text: &str, input: &str) -> String {
+    let mut output: Vec<u8> = Vec::new();
+
+    medusa_lang::interpret_from_text(source_text, &mut input.as_bytes(), &mut output).unwrap();
+
+    String::from_utf8(output).unwrap()
+}
+
+// Retrieve only the program-specific output from a medusa program run by the interpreter, stripped the same
+//  way as compile_and_get_stripped_output
+#[allow(dead_code)]
+pub fn interpret_and_get_stripped_output(source_text: &str, input: &str) -> String {
+    strip_output(interpret_and_get_output(source_text, input))
+}
 
+fn strip_output(output: String) -> String {
     let medusa_version = env!("CARGO_PKG_VERSION");
     let header = regex::Regex::new(format!("Medusa {medusa_version}").as_str()).unwrap();
     let footer = regex::Regex::new(r"Program ended").unwrap();
@@ -48,5 +153,5 @@ pub fn compile_and_get_stripped_output(source_text: &str, test_name: &str) -> St
         .replace_all(output.as_str(), "")
         .to_string();
 
-    return output;
+    output
 }
@@ -58,6 +58,34 @@ fn float_to_string_4() {
     );
 }
 
+#[test]
+fn float_to_string_5() {
+    // Floats that don't fit in an int (including NaN and infinity) all print the same way, and the interpreter
+    // has to agree with the compiled program
+    let program = r#"
+float zero = 0.0;
+float big = 10.0 ^ 20.0;
+zero / zero -> @;
+1.0 / zero -> @;
+-1.0 / zero -> @;
+big -> @;
+"#;
+
+    let expected_output = "-9223372036854775808.775808\
+9223372036854775808.775808\
+-9223372036854775808.775808\
+9223372036854775808.775808";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
 #[test]
 fn string_to_float_1() {
     let program = r#"
@@ -178,6 +206,26 @@ x -> @;
     );
 }
 
+#[test]
+fn string_to_float_9() {
+    // The conversion stops at the first null character, even though the string carries on after it
+    let program = r#"
+float x = (float) "1\x002";
+x -> @;
+"#;
+
+    let expected_output = "1.000000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
 #[test]
 fn string_to_int_1() {
     let program = r#"
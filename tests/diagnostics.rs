@@ -0,0 +1,335 @@
+use medusa_lang::{ErrorKind, Position, RuntimeError};
+
+#[test]
+fn multiple_errors() {
+    let program = r#"
+int x = y;
+string s = "a" .. 5;
+z -> @;
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let kinds: Vec<ErrorKind> = error.diagnostics.iter().map(|x| x.kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            ErrorKind::UndeclaredVariable,
+            ErrorKind::TypeMismatch,
+            ErrorKind::UndeclaredVariable
+        ]
+    );
+}
+
+#[test]
+fn no_cascading_errors() {
+    // The undeclared variable poisons the whole expression, so there is no mismatched datatype error
+    let program = r#"
+int x = (y + 1.5) * 2;
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    assert_eq!(error.diagnostics.len(), 1);
+    assert_eq!(error.diagnostics[0].kind, ErrorKind::UndeclaredVariable);
+}
+
+#[test]
+fn bool_errors() {
+    let program = r#"
+int x = 5;
+if (x) {
+    "x" -> @;
+};
+bool b = x && true;
+bool c = true < false;
+int y = (int) b;
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let messages: Vec<&str> = error
+        .diagnostics
+        .iter()
+        .map(|x| x.message.as_str())
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            "Condition is not a bool",
+            "Logical operation on non-bool",
+            "Bools can't be ordered",
+            "Bools can only be cast to string"
+        ]
+    );
+}
+
+#[test]
+fn function_errors() {
+    let program = r#"
+int add(int a, int b) {
+    return a + b;
+};
+
+int add(int x) {
+    return x;
+};
+
+int maybe(int x) {
+    if (x > 0) {
+        return 1;
+    };
+};
+
+int y = add(1);
+int z = add(1, "2");
+string s = add(1, 2);
+missing(5) -> @;
+return 5;
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let kinds: Vec<ErrorKind> = error.diagnostics.iter().map(|x| x.kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            ErrorKind::Redeclaration,
+            ErrorKind::MissingReturn,
+            ErrorKind::WrongArgumentCount,
+            ErrorKind::TypeMismatch,
+            ErrorKind::TypeMismatch,
+            ErrorKind::UndeclaredFunction,
+            ErrorKind::InvalidStatement
+        ]
+    );
+
+    assert_eq!(
+        error.diagnostics[1].label.as_deref(),
+        Some("this must return a value of type int")
+    );
+    assert_eq!(
+        error.diagnostics[2].notes,
+        vec!["`add` is declared as `int add(int a, int b)`"]
+    );
+}
+
+#[test]
+fn scope_errors() {
+    let program = r#"
+int x = 1;
+string x = "again";
+if (x > 0) {
+    int y = 2;
+    int y = 3;
+};
+y -> @;
+for (int i = 0; i < 3; i = i + 1) {
+    i -> @;
+};
+i -> @;
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let kinds: Vec<ErrorKind> = error.diagnostics.iter().map(|x| x.kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            ErrorKind::Redeclaration,
+            ErrorKind::Redeclaration,
+            ErrorKind::UndeclaredVariable,
+            ErrorKind::UndeclaredVariable
+        ]
+    );
+}
+
+#[test]
+fn array_errors() {
+    let program = r#"
+int[3] values;
+int x = 1;
+values = 5;
+values -> @;
+x[0] = 1;
+values[0] = "text";
+values["zero"] -> @;
+missing[0] -> @;
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let kinds: Vec<ErrorKind> = error.diagnostics.iter().map(|x| x.kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            ErrorKind::TypeMismatch,
+            ErrorKind::TypeMismatch,
+            ErrorKind::TypeMismatch,
+            ErrorKind::TypeMismatch,
+            ErrorKind::TypeMismatch,
+            ErrorKind::UndeclaredVariable
+        ]
+    );
+
+    assert_eq!(
+        error.diagnostics[3].notes,
+        vec!["`values` was declared as an array of int"]
+    );
+}
+
+#[test]
+fn array_index_out_of_bounds() {
+    let program = "int[2] values;\nvalues[2] -> @;\n";
+
+    let mut output: Vec<u8> = Vec::new();
+    let error =
+        medusa_lang::interpret_from_text(program, &mut "".as_bytes(), &mut output).unwrap_err();
+
+    assert_eq!(
+        error.diagnostics[0].kind,
+        ErrorKind::Runtime(RuntimeError::IndexOutOfBounds)
+    );
+    assert_eq!(
+        error.diagnostics[0].range(program),
+        Some((
+            Position { line: 2, column: 8 },
+            Position { line: 2, column: 9 }
+        ))
+    );
+}
+
+#[test]
+fn string_input_else() {
+    let program = r#"
+string name <- @ else {
+    "Never happens" -> @;
+};
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    assert_eq!(error.diagnostics[0].kind, ErrorKind::InvalidStatement);
+    assert_eq!(error.diagnostics[0].message, "Reading a string can't fail");
+}
+
+#[test]
+fn line_and_column_range() {
+    let program = "int x = 5;\nx = x .. \"a\";\n";
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    assert_eq!(
+        error.diagnostics[0].range(program),
+        Some((
+            Position { line: 2, column: 5 },
+            Position {
+                line: 2,
+                column: 13
+            }
+        ))
+    );
+}
+
+#[test]
+fn rendered_snippet() {
+    let program = "int x = 5;\ny -> @;\n";
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let expected_output = "\
+error: Variable used but not declared
+ --> example.med:2:1
+  |
+2 | y -> @;
+  | ^ not declared
+  |
+  = help: Declare the variable before using it, e.g. `int y;`
+";
+
+    assert_eq!(error.render("example.med", program), expected_output);
+}
+
+#[test]
+fn compound_assignment_errors() {
+    let program = r#"
+int x = 1;
+float f = 1.5;
+string s = "s";
+x += 1.5;
+f %= 2.0;
+s++;
+s += "t";
+y++;
+x += y;
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let kinds: Vec<ErrorKind> = error.diagnostics.iter().map(|x| x.kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            ErrorKind::TypeMismatch,
+            ErrorKind::TypeMismatch,
+            ErrorKind::TypeMismatch,
+            ErrorKind::TypeMismatch,
+            ErrorKind::UndeclaredVariable,
+            ErrorKind::UndeclaredVariable
+        ]
+    );
+
+    assert_eq!(error.diagnostics[2].message, "Math operation on non-number");
+    assert_eq!(error.diagnostics[2].label, Some("string++".to_string()));
+}
+
+#[test]
+fn break_outside_of_loop() {
+    let program = r#"
+break;
+int f() {
+    continue;
+    return 1;
+};
+while (true) {
+    break;
+};
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let kinds: Vec<ErrorKind> = error.diagnostics.iter().map(|x| x.kind).collect();
+
+    assert_eq!(kinds, vec![ErrorKind::InvalidStatement; 2]);
+    assert_eq!(error.diagnostics[0].message, "`break` outside of a loop");
+    assert_eq!(error.diagnostics[1].message, "`continue` outside of a loop");
+}
+
+#[test]
+fn invalid_escape_sequences() {
+    let cases = [
+        (r#""a\qb""#, r"\q"),
+        (r#""\x4""#, r"\x4"),
+        (r#""\xG1""#, r"\x"),
+        (r#""\u{110000}""#, r"\u{110000}"),
+        (r#""\u{41""#, r"\u{41"),
+        (r#""\u41""#, r"\u"),
+    ];
+
+    for (literal, escape) in cases {
+        let source = format!("string s = {literal};");
+
+        let error = medusa_lang::check_from_text(&source).unwrap_err();
+
+        assert_eq!(error.diagnostics[0].kind, ErrorKind::InvalidLiteral);
+
+        let span = error.diagnostics[0].span.unwrap();
+        assert_eq!(&source[span.start..span.end], escape, "{literal}");
+    }
+}
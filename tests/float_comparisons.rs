@@ -0,0 +1,411 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn float_greater_1() {
+    let program = r#"
+if (1.5 > 0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (0.5 > 1.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (2.25 > 2.25) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (-1.0 > -0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "TFFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_greater_2() {
+    // NaN is unordered, so every comparison with it is false except for !=
+    let program = r#"
+float zero = 0.0;
+float nan = zero / zero;
+float x = 1.0;
+if (nan > x) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (x > nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (nan > nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_less_1() {
+    let program = r#"
+if (1.5 < 0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (0.5 < 1.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (2.25 < 2.25) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (-1.0 < -0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FTFT";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_less_2() {
+    // NaN is unordered, so every comparison with it is false except for !=
+    let program = r#"
+float zero = 0.0;
+float nan = zero / zero;
+float x = 1.0;
+if (nan < x) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (x < nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (nan < nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_greater_or_equal_1() {
+    let program = r#"
+if (1.5 >= 0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (0.5 >= 1.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (2.25 >= 2.25) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (-1.0 >= -0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "TFTF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_greater_or_equal_2() {
+    // NaN is unordered, so every comparison with it is false except for !=
+    let program = r#"
+float zero = 0.0;
+float nan = zero / zero;
+float x = 1.0;
+if (nan >= x) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (x >= nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (nan >= nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_less_or_equal_1() {
+    let program = r#"
+if (1.5 <= 0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (0.5 <= 1.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (2.25 <= 2.25) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (-1.0 <= -0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FTTT";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_less_or_equal_2() {
+    // NaN is unordered, so every comparison with it is false except for !=
+    let program = r#"
+float zero = 0.0;
+float nan = zero / zero;
+float x = 1.0;
+if (nan <= x) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (x <= nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (nan <= nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_equal_1() {
+    let program = r#"
+if (1.5 == 0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (0.5 == 1.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (2.25 == 2.25) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (-1.0 == -0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FFTF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_equal_2() {
+    // NaN is unordered, so every comparison with it is false except for !=
+    let program = r#"
+float zero = 0.0;
+float nan = zero / zero;
+float x = 1.0;
+if (nan == x) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (x == nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (nan == nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_not_equal_1() {
+    let program = r#"
+if (1.5 != 0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (0.5 != 1.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (2.25 != 2.25) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (-1.0 != -0.5) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "TTFT";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_not_equal_2() {
+    // NaN is unordered, so every comparison with it is false except for !=
+    let program = r#"
+float zero = 0.0;
+float nan = zero / zero;
+float x = 1.0;
+if (nan != x) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (x != nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (nan != nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "TTT";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_comparison_forloop() {
+    let program = r#"
+float total = 0.0;
+for (float x = 0.0; x <= 1.0; x = x + 0.25) {
+    total = total + x;
+};
+total -> @;
+"#;
+
+    let expected_output = "2.500000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
@@ -0,0 +1,198 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn float_power_1() {
+    let program = r#"
+float x = 2.0;
+x ^ 10.0 -> @;
+"#;
+
+    let expected_output = "1024.000000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_2() {
+    let program = r#"
+float x = -3.0;
+x ^ 3.0 -> @;
+"#;
+
+    let expected_output = "-27.000000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_3() {
+    let program = r#"
+float x = 5.0;
+x ^ 0.0 -> @;
+"#;
+
+    let expected_output = "1.000000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_4() {
+    let program = r#"
+float x = 2.0;
+x ^ -2.0 -> @;
+"#;
+
+    let expected_output = "0.250000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_5() {
+    let program = r#"
+float x = 2.0;
+x ^ 0.5 -> @;
+"#;
+
+    let expected_output = "1.414213";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_6() {
+    let program = r#"
+float x = 10.0;
+x ^ -1.5 -> @;
+"#;
+
+    let expected_output = "0.031622";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_7() {
+    let program = r#"
+float x = 2.7;
+x ^ 3.3 -> @;
+"#;
+
+    let expected_output = "26.515536";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_8() {
+    // 0 to a negative power is infinity
+    let program = r#"
+float zero = 0.0;
+float infinity = 1.0 / zero;
+float x = zero ^ -1.0;
+if (x == infinity) {
+    "infinity" -> @;
+};
+"#;
+
+    let expected_output = "infinity";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_9() {
+    // A negative number to a fractional power is NaN
+    let program = r#"
+float x = -2.0 ^ 0.5;
+if (x != x) {
+    "NaN" -> @;
+};
+"#;
+
+    let expected_output = "NaN";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_10() {
+    // x^0 and 1^y are 1 even when the other side is NaN
+    let program = r#"
+float zero = 0.0;
+float nan = zero / zero;
+nan ^ 0.0 -> @;
+1.0 ^ nan -> @;
+"#;
+
+    let expected_output = "1.0000001.000000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power_11() {
+    let program = r#"
+float zero = 0.0;
+float infinity = 1.0 / zero;
+0.5 ^ infinity -> @;
+-1.0 ^ infinity -> @;
+"#;
+
+    let expected_output = "0.0000001.000000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn float_negation() {
+    let program = r#"
+float x = 1.5;
+-x -> @;
+-(x * 2.0) -> @;
+x-0.5 -> @;
+"#;
+
+    let expected_output = "-1.500000-3.0000001.000000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
@@ -0,0 +1,160 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn function_1() {
+    let program = r#"
+int add(int a, int b) {
+    return a + b;
+};
+
+add(2, 3) -> @;
+add(add(1, 2), 10) * 2 -> @;
+"#;
+
+    let expected_output = "526";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn function_2() {
+    let program = r#"
+// Functions can be called before they're declared
+square(1.5) -> @;
+
+float square(float x) {
+    return x * x;
+};
+"#;
+
+    let expected_output = "2.250000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn recursion_1() {
+    let program = r#"
+int factorial(int n) {
+    if (n <= 1) {
+        return 1;
+    };
+    return n * factorial(n - 1);
+};
+
+factorial(10) -> @;
+"#;
+
+    let expected_output = "3628800";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn recursion_2() {
+    let program = r#"
+int fibonacci(int n) {
+    if (n < 2) {
+        return n;
+    } else {
+        int previous = fibonacci(n - 1);
+        return previous + fibonacci(n - 2);
+    };
+};
+
+for (int i = 0; i < 10; i = i + 1) {
+    fibonacci(i) -> @;
+};
+"#;
+
+    // Each call has its own copy of previous, so the recursive calls don't overwrite it
+    let expected_output = "0112358132134";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn string_parameters() {
+    let program = r#"
+string greet(string name, int times) {
+    string result = "";
+    for (int i = 0; i < times; i = i + 1) {
+        result = result .. "Hi " .. name .. "! ";
+    };
+    return result;
+};
+
+string name = "Bob";
+greet(name, 2) -> @;
+name -> @;
+"#;
+
+    let expected_output = "Hi Bob! Hi Bob! Bob";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn local_variables() {
+    let program = r#"
+bool isEven(int n) {
+    int half = n / 2;
+    return half * 2 == n;
+};
+
+// The function's variables are separate from the ones outside of it
+int half = 100;
+isEven(7) -> @;
+isEven(half) -> @;
+half -> @;
+"#;
+
+    let expected_output = "falsetrue100";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn return_from_loop() {
+    let program = r#"
+int firstSquareAbove(int limit) {
+    int i = 0;
+    while (true) {
+        if (i * i > limit) {
+            return i * i;
+        };
+        i = i + 1;
+    };
+    return 0;
+};
+
+firstSquareAbove(50) -> @;
+"#;
+
+    let expected_output = "64";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
@@ -0,0 +1,69 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn else_if_1() {
+    let program = r#"
+for (int i = 0; i < 4; i = i + 1) {
+    if (i == 0) {
+        "zero" -> @;
+    } else if (i == 1) {
+        "one" -> @;
+    } else if (i == 2) {
+        "two" -> @;
+    } else {
+        "many" -> @;
+    };
+};
+"#;
+
+    let expected_output = "zeroonetwomany";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn else_if_2() {
+    let program = r#"
+int x = 5;
+if (x > 10) {
+    "big" -> @;
+} else if (x > 1) {
+    "medium" -> @;
+} else if (x > 0) {
+    "small" -> @;
+};
+"#;
+
+    // Only the first true branch runs, even though the later conditions are true too
+    let expected_output = "medium";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn else_if_3() {
+    let program = r#"
+float x = 0.5;
+if (x > 1.0) {
+    "big" -> @;
+} else if (x < 0.0) {
+    "negative" -> @;
+};
+"done" -> @;
+"#;
+
+    let expected_output = "done";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
@@ -91,3 +91,133 @@ x - 8 -> @;
         expected_output
     );
 }
+
+#[test]
+fn integer_power_1() {
+    let program = r#"
+int x = 2;
+x ^ 10 -> @;
+"#;
+
+    let expected_output = "1024";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn integer_power_2() {
+    let program = r#"
+int x = -3;
+x ^ 3 -> @;
+"#;
+
+    let expected_output = "-27";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn integer_power_3() {
+    let program = r#"
+int x = 5;
+x ^ 0 -> @;
+"#;
+
+    let expected_output = "1";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn integer_power_4() {
+    // Negative powers of integers are always truncated to 0
+    let program = r#"
+int x = 2;
+x ^ -1 -> @;
+"#;
+
+    let expected_output = "0";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn negation() {
+    let program = r#"
+int x = 5;
+int y = 2;
+x-1 -> @;
+-x -> @;
+-(x + y) -> @;
+x - -y -> @;
++x -> @;
+- -x -> @;
+(string)-3 -> @;
+"#;
+
+    let expected_output = "4-5-7755-3";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn power_is_right_associative() {
+    let program = r#"
+2 ^ 3 ^ 2 -> @;
+(2 ^ 3) ^ 2 -> @;
+10 - 4 - 3 -> @;
+"#;
+
+    let expected_output = "512643";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn compound_assignment() {
+    let program = r#"
+int x = 5;
+x += 3 * 2;
+x -> @;
+x -= 1;
+x -> @;
+x *= 2 + 1;
+x -> @;
+x /= 4;
+x -> @;
+x %= 5;
+x -> @;
+x ^= 3;
+x -> @;
+x++;
+x -> @;
+x--;
+x--;
+x -> @;
+"#;
+
+    let expected_output = "11103072897";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
@@ -0,0 +1,458 @@
+mod common;
+
+#[test]
+fn hello_world() {
+    let program = r#"
+"Hello, world!" -> @;
+"#;
+
+    let expected_output = format!(
+        "Medusa {}\n\0Hello, world!\nProgram ended\n\0",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    assert_eq!(
+        common::interpret_and_get_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn operator_precedence() {
+    let program = r#"
+int x = 2 + 3 * 4;
+x -> @;
+"#;
+
+    let expected_output = "14";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn input_conversion() {
+    let program = r#"
+int x <- @;
+float y <- @;
+string s <- @;
+x * 2 -> @;
+y -> @;
+s .. "!" -> @;
+"#;
+
+    let input = "21\r\n-1.5\nhello\n";
+
+    let expected_output = "42-1.500000hello!";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, input),
+        expected_output
+    );
+}
+
+#[test]
+fn if_else() {
+    let program = r#"
+int x = 5;
+if (x > 3) {
+    "big" -> @;
+} else {
+    "small" -> @;
+};
+if (x < 3) {
+    "big" -> @;
+} else {
+    "small" -> @;
+};
+"#;
+
+    let expected_output = "bigsmall";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn else_if() {
+    let program = r#"
+for (int i = 0; i < 4; i = i + 1) {
+    if (i == 0) {
+        "zero" -> @;
+    } else if (i == 1) {
+        "one" -> @;
+    } else if (i == 2) {
+        "two" -> @;
+    } else {
+        "many" -> @;
+    };
+};
+"#;
+
+    let expected_output = "zeroonetwomany";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn forloop() {
+    let program = r#"
+int total = 0;
+for (int i = 1; i < 11; i = i + 1) {
+    total = total + i;
+};
+total -> @;
+"#;
+
+    let expected_output = "55";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn while_loop() {
+    let program = r#"
+int n = 27;
+int steps = 0;
+while (n != 1) {
+    if (n % 2 == 0) {
+        n = n / 2;
+    } else {
+        n = 3 * n + 1;
+    };
+    steps = steps + 1;
+};
+steps -> @;
+"#;
+
+    let expected_output = "111";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn bool_operations() {
+    let program = r#"
+int x = 0;
+bool safe = x == 0 || 10 / x > 1;
+safe -> @;
+if (x != 0 && 10 / x > 1) {
+    "divided" -> @;
+};
+true || false && false -> @;
+!(x < 1) -> @;
+"x is zero: " .. (string) (x == 0) -> @;
+"#;
+
+    let expected_output = "truetruefalsex is zero: true";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn functions() {
+    let program = r#"
+int fibonacci(int n) {
+    if (n < 2) {
+        return n;
+    } else {
+        int previous = fibonacci(n - 1);
+        return previous + fibonacci(n - 2);
+    };
+};
+
+string repeat(string text, int times) {
+    string result = "";
+    for (int i = 0; i < times; i = i + 1) {
+        result = result .. text;
+    };
+    return result;
+};
+
+int n = 15;
+fibonacci(n) -> @;
+repeat("ab", 3) -> @;
+n -> @;
+"#;
+
+    let expected_output = "610ababab15";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn scoping() {
+    let program = r#"
+int x = 1;
+for (int i = 0; i < 3; i = i + 1) {
+    int count;
+    count = count + 1;
+    string x = "inner";
+    x .. (string) count -> @;
+};
+x -> @;
+"#;
+
+    let expected_output = "inner1inner1inner11";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn guessing_game() {
+    let program = include_str!("../examples/guessing_game.med");
+
+    let input = "50\n1\n4\n";
+
+    let expected_output = "I'm thinking of a number between 1 and 100. Can you guess it?\
+Hmm.. nope, guess lower!\
+Hmm.. nope, guess higher!\
+You did it! The number was 4!\
+Well, that was fun - let's play again another day :)";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, input),
+        expected_output
+    );
+}
+
+#[test]
+fn division_by_zero() {
+    let program = r#"
+int x = 0;
+5 / x -> @;
+"#;
+
+    let mut output: Vec<u8> = Vec::new();
+
+    assert!(medusa_lang::interpret_from_text(program, &mut "".as_bytes(), &mut output).is_err());
+}
+
+#[test]
+fn undeclared_variable() {
+    let program = r#"
+x -> @;
+"#;
+
+    let mut output: Vec<u8> = Vec::new();
+
+    assert!(medusa_lang::interpret_from_text(program, &mut "".as_bytes(), &mut output).is_err());
+}
+
+#[test]
+fn float_comparisons() {
+    let program = r#"
+float zero = 0.0;
+float nan = zero / zero;
+if (1.5 >= 1.5) {
+    "T" -> @;
+};
+if (nan == nan) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+if (nan != nan) {
+    "T" -> @;
+};
+"#;
+
+    let expected_output = "TFT";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn string_comparisons() {
+    let program = r#"
+string answer <- @;
+if (answer == "yes") {
+    "yes" -> @;
+};
+if (answer != "no") {
+    "not no" -> @;
+};
+if ("app" < "apple") {
+    "prefix first" -> @;
+};
+"#;
+
+    let expected_output = "yesnot noprefix first";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, "yes\n"),
+        expected_output
+    );
+}
+
+#[test]
+fn float_power() {
+    let program = r#"
+2.0 ^ 0.5 -> @;
+-3.0 ^ 3.0 -> @;
+2.0 ^ -2.0 -> @;
+float zero = 0.0;
+float x = zero ^ -1.0;
+if (x > 1000000.0) {
+    "infinity" -> @;
+};
+"#;
+
+    let expected_output = "1.414213-27.0000000.250000infinity";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn arrays() {
+    let program = r#"
+int[4] values;
+string[2] words;
+for (int i = 0; i < #values; i = i + 1) {
+    values[i] = i * 10;
+};
+words[1] = "b";
+values[3] -> @;
+words[0] .. words[1] -> @;
+#words -> @;
+"#;
+
+    let expected_output = "30b2";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn invalid_input() {
+    let program = r#"
+int number;
+number <- @ else {
+    "Not a number" -> @;
+};
+number -> @;
+int other <- @;
+"#;
+
+    let mut output: Vec<u8> = Vec::new();
+    let error = medusa_lang::interpret_from_text(program, &mut "abc\n+\n".as_bytes(), &mut output)
+        .unwrap_err();
+
+    assert_eq!(
+        error.diagnostics[0].kind,
+        medusa_lang::ErrorKind::Runtime(medusa_lang::RuntimeError::InvalidInput(
+            medusa_lang::VariableDataType::INT
+        ))
+    );
+    assert_eq!(error.diagnostics[0].message, "Input is not a valid int");
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("Not a number\n0\n"));
+}
+
+#[test]
+fn negation() {
+    let program = r#"
+int x = 5;
+x-1 -> @;
+-(x + 2) -> @;
+-(1.5 * 2.0) -> @;
+"#;
+
+    let expected_output = "4-7-3.000000";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn compound_assignment() {
+    let program = r#"
+int total = 0;
+string s = "";
+for (int i = 1; i < 4; i++) {
+    total *= 2;
+    total += i;
+    s ..= (string)i;
+};
+total -> @;
+s -> @;
+"#;
+
+    let expected_output = "11123";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn break_and_continue() {
+    let program = r#"
+int total = 0;
+for (int i = 0; i < 10; i++) {
+    if (i == 3) {
+        continue;
+    };
+    if (i == 6) {
+        break;
+    };
+    total += i;
+};
+total -> @;
+"#;
+
+    let expected_output = "12";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn escape_sequences() {
+    let program = r#"
+"tab\there \"quoted\" \x41\u{42}" -> @;
+"#;
+
+    let expected_output = "tab\there \"quoted\" AB";
+
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
@@ -0,0 +1,166 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn while_loop_1() {
+    let program = r#"
+int total = 0;
+int i = 1;
+while (i < 11) {
+    total = total + i;
+    i = i + 1;
+};
+total -> @;
+"#;
+
+    let expected_output = "55";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn while_loop_2() {
+    let program = r#"
+int i = 10;
+while (i < 5) {
+    "never" -> @;
+};
+i -> @;
+"#;
+
+    let expected_output = "10";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn while_loop_3() {
+    let program = r#"
+int i = 0;
+while (i < 3) {
+    int j = 0;
+    while (j < 2) {
+        i * 10 + j -> @;
+        " " -> @;
+        j = j + 1;
+    };
+    i = i + 1;
+};
+"#;
+
+    let expected_output = "0 1 10 11 20 21 ";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn while_loop_4() {
+    let program = r#"
+string s = "";
+while (s != "aaaa") {
+    s = s .. "a";
+};
+s -> @;
+"#;
+
+    let expected_output = "aaaa";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn compound_assignment_step() {
+    let program = r#"
+int total = 0;
+for (int i = 0; i < 5; i++) {
+    total += i;
+};
+for (int i = 10; i > 0; i -= 3) {
+    i -> @;
+};
+total -> @;
+"#;
+
+    let expected_output = "1074110";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn break_and_continue() {
+    let program = r#"
+for (int i = 0; i < 10; i++) {
+    if (i % 2 == 1) {
+        continue;
+    };
+    if (i > 6) {
+        break;
+    };
+    i -> @;
+};
+int j = 0;
+while (true) {
+    j++;
+    if (j < 3) {
+        continue;
+    };
+    for (int k = 0; k < 5; k++) {
+        if (k == 2) {
+            break;
+        };
+        k -> @;
+    };
+    if (j == 4) {
+        break;
+    };
+};
+j -> @;
+"#;
+
+    let expected_output = "024601014";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn break_in_function() {
+    let program = r#"
+int firstSquareAbove(int n) {
+    int i = 0;
+    while (true) {
+        if (i * i > n) {
+            break;
+        };
+        i++;
+    };
+    return i * i;
+};
+firstSquareAbove(10) -> @;
+"#;
+
+    let expected_output = "16";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
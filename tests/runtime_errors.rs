@@ -0,0 +1,285 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn division_by_zero() {
+    let program = r#"
+int x = 10;
+int y = 0;
+x / 2 -> @;
+x / y -> @;
+"#;
+
+    let (exit_code, output) =
+        common::compile_and_get_exit_code_and_stripped_output(program, function_name!(), "");
+
+    assert_eq!(exit_code, 4);
+    assert_eq!(
+        output,
+        "5Runtime error on line 5: Division by zero5 | x / y -> @;"
+    );
+}
+
+#[test]
+fn modulo_by_zero() {
+    let program = r#"
+int y = 0;
+int z = 7 % y;
+"#;
+
+    let (exit_code, output) =
+        common::compile_and_get_exit_code_and_stripped_output(program, function_name!(), "");
+
+    assert_eq!(exit_code, 4);
+    assert_eq!(
+        output,
+        "Runtime error on line 3: Division by zero3 | int z = 7 % y;"
+    );
+}
+
+#[test]
+fn invalid_int_input() {
+    let program = r#"
+int x <- @;
+x -> @;
+int y <- @;
+y -> @;
+"#;
+
+    let (exit_code, output) = common::compile_and_get_exit_code_and_stripped_output(
+        program,
+        function_name!(),
+        "-12\nabc\n",
+    );
+
+    assert_eq!(exit_code, 5);
+    assert_eq!(
+        output,
+        "-12Runtime error on line 4: Input is not a valid int4 | int y <- @;"
+    );
+}
+
+#[test]
+fn invalid_float_input() {
+    let program = r#"
+float x;
+x <- @;
+"#;
+
+    let (exit_code, output) =
+        common::compile_and_get_exit_code_and_stripped_output(program, function_name!(), ".\n");
+
+    assert_eq!(exit_code, 5);
+    assert_eq!(
+        output,
+        "Runtime error on line 3: Input is not a valid float3 | x <- @;"
+    );
+}
+
+#[test]
+fn input_else() {
+    let program = r#"
+// Keep asking until a number is typed in
+bool valid = false;
+int number;
+while (!valid) {
+    valid = true;
+    number <- @ else {
+        "Not a number, try again" -> @;
+        valid = false;
+    };
+};
+number * 2 -> @;
+float f <- @ else {
+    "Not a float either" -> @;
+};
+f -> @;
+"#;
+
+    let expected_output =
+        "Not a number, try againNot a number, try again42Not a float either0.000000";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output_with_input(
+            program,
+            function_name!(),
+            "\nforty\n21\n1.2.3\n"
+        ),
+        expected_output
+    );
+}
+
+#[test]
+fn integer_overflow() {
+    let program = r#"
+int x = 9223372036854775807;
+x - 1 -> @;
+x + 1 -> @;
+"#;
+
+    let options = medusa_lang::CompileOptions {
+        overflow_checks: true,
+    };
+    let (exit_code, output) = common::compile_and_get_exit_code_and_stripped_output_with_options(
+        program,
+        function_name!(),
+        "",
+        options,
+    );
+
+    assert_eq!(exit_code, 6);
+    assert_eq!(
+        output,
+        "9223372036854775806Runtime error on line 4: Integer overflow4 | x + 1 -> @;"
+    );
+}
+
+#[test]
+fn integer_overflow_in_multiplication_and_power() {
+    let options = medusa_lang::CompileOptions {
+        overflow_checks: true,
+    };
+
+    let (exit_code, output) = common::compile_and_get_exit_code_and_stripped_output_with_options(
+        "int x = 4294967296;\nx * x -> @;\n",
+        &format!("{}_multiply", function_name!()),
+        "",
+        options,
+    );
+
+    assert_eq!(exit_code, 6);
+    assert_eq!(
+        output,
+        "Runtime error on line 2: Integer overflow2 | x * x -> @;"
+    );
+
+    let (exit_code, output) = common::compile_and_get_exit_code_and_stripped_output_with_options(
+        "2 ^ 62 -> @;\n2 ^ 63 -> @;\n",
+        &format!("{}_power", function_name!()),
+        "",
+        options,
+    );
+
+    assert_eq!(exit_code, 6);
+    assert_eq!(
+        output,
+        "4611686018427387904Runtime error on line 2: Integer overflow2 | 2 ^ 63 -> @;"
+    );
+}
+
+#[test]
+fn integer_overflow_in_division() {
+    let program = r#"
+int x = -9223372036854775807 - 1;
+x % -1 -> @;
+x / -1 -> @;
+"#;
+
+    let options = medusa_lang::CompileOptions {
+        overflow_checks: true,
+    };
+    let (exit_code, output) = common::compile_and_get_exit_code_and_stripped_output_with_options(
+        program,
+        function_name!(),
+        "",
+        options,
+    );
+
+    assert_eq!(exit_code, 6);
+    assert_eq!(
+        output,
+        "0Runtime error on line 4: Integer overflow4 | x / -1 -> @;"
+    );
+}
+
+#[test]
+fn integer_overflow_in_negation() {
+    // Only a minus sign straight in front of a literal is folded, so this is negated (and checked) at runtime
+    let program = r#"
+-9223372036854775808 -> @;
+-(-9223372036854775808) -> @;
+"#;
+
+    let options = medusa_lang::CompileOptions {
+        overflow_checks: true,
+    };
+    let (exit_code, output) = common::compile_and_get_exit_code_and_stripped_output_with_options(
+        program,
+        function_name!(),
+        "",
+        options,
+    );
+
+    assert_eq!(exit_code, 6);
+    assert_eq!(
+        output,
+        "-9223372036854775808Runtime error on line 3: Integer overflow3 | -(-9223372036854775808) -> @;"
+    );
+}
+
+#[test]
+fn integer_overflow_wraps_by_default() {
+    let program = r#"
+int x = 9223372036854775807;
+x + 1 -> @;
+"#;
+
+    let (exit_code, output) =
+        common::compile_and_get_exit_code_and_stripped_output(program, function_name!(), "");
+
+    assert_eq!(exit_code, 0);
+    assert_eq!(output, "-9223372036854775808");
+}
+
+#[test]
+fn division_by_negative_one() {
+    // idiv faults on the smallest int divided by -1, so the quotient wraps around instead
+    let program = r#"
+int x = -9223372036854775807 - 1;
+int y = -1;
+x / y -> @;
+x % y -> @;
+7 / y -> @;
+7 % y -> @;
+"#;
+
+    let (exit_code, output) =
+        common::compile_and_get_exit_code_and_stripped_output(program, function_name!(), "");
+
+    assert_eq!(exit_code, 0);
+    assert_eq!(output, "-92233720368547758080-70");
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        output
+    );
+}
+
+#[test]
+fn element_assignment_out_of_bounds() {
+    // The value is worked out before the index is checked, so its side effects happen either way
+    let program = r#"
+int[2] values;
+int f() {
+    "called" -> @;
+    return 1;
+};
+values[5] = f();
+"#;
+
+    let (exit_code, output) =
+        common::compile_and_get_exit_code_and_stripped_output(program, function_name!(), "");
+
+    assert_eq!(exit_code, 2);
+    assert_eq!(
+        output,
+        "calledRuntime error on line 7: Array index out of bounds7 | values[5] = f();"
+    );
+
+    let mut output: Vec<u8> = Vec::new();
+    let error =
+        medusa_lang::interpret_from_text(program, &mut "".as_bytes(), &mut output).unwrap_err();
+
+    assert_eq!(error.exit_code(), 2);
+    assert!(String::from_utf8(output).unwrap().contains("called\n"));
+}
@@ -0,0 +1,164 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn shadowing_1() {
+    let program = r#"
+int x = 1;
+if (x == 1) {
+    string x = "inner";
+    x -> @;
+};
+x -> @;
+"#;
+
+    let expected_output = "inner1";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn shadowing_2() {
+    let program = r#"
+int x = 1;
+while (x < 3) {
+    // Assigning to x from an inner scope changes the outer variable
+    x = x + 1;
+    int y = x * 10;
+    if (true) {
+        int y = 5;
+        y -> @;
+    };
+    y -> @;
+};
+x -> @;
+"#;
+
+    let expected_output = "5205303";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn fresh_variable_each_iteration() {
+    let program = r#"
+for (int i = 0; i < 3; i = i + 1) {
+    int count;
+    string text;
+    count = count + 1;
+    text = text .. "a";
+    count -> @;
+    text -> @;
+};
+"#;
+
+    let expected_output = "1a1a1a";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn loop_variable_reuse() {
+    let program = r#"
+for (int i = 0; i < 2; i = i + 1) {
+    i -> @;
+};
+for (int i = 5; i < 7; i = i + 1) {
+    i -> @;
+};
+"#;
+
+    let expected_output = "0156";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn shadowing_in_functions() {
+    let program = r#"
+int sumTo(int n) {
+    int total = 0;
+    for (int i = 1; i <= n; i = i + 1) {
+        int total = i;
+        total = total * 100;
+    };
+    for (int i = 1; i <= n; i = i + 1) {
+        total = total + i;
+    };
+    return total;
+};
+
+sumTo(4) -> @;
+"#;
+
+    let expected_output = "10";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn initializer_refers_to_outer_variable() {
+    // The starting value is checked before the new variable exists, so the x on the right is the outer one
+    let program = r#"
+int x = 5;
+if (true) {
+    int x = x + 1;
+    x -> @;
+};
+for (int i = 0; i < 3; i++) {
+    int x = x + i;
+    x -> @;
+};
+x -> @;
+"#;
+
+    let expected_output = "65675";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
+
+#[test]
+fn initializer_cant_refer_to_itself() {
+    let program = r#"
+int x = 5;
+if (true) {
+    string x = x .. "!";
+};
+int y = y + 1;
+"#;
+
+    let error = medusa_lang::check_from_text(program).unwrap_err();
+
+    let kinds: Vec<medusa_lang::ErrorKind> = error.diagnostics.iter().map(|x| x.kind).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            medusa_lang::ErrorKind::TypeMismatch,
+            medusa_lang::ErrorKind::UndeclaredVariable
+        ]
+    );
+}
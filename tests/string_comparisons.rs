@@ -0,0 +1,428 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn string_equal() {
+    let program = r#"
+string left = "";
+string right = "";
+left = "apple";
+right = "apple";
+if (left == right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "banana";
+if (left == right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "banana";
+right = "apple";
+if (left == right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "app";
+right = "apple";
+if (left == right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "app";
+if (left == right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "";
+right = "a";
+if (left == right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "Zebra";
+right = "apple";
+if (left == right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "TFFFFFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn string_not_equal() {
+    let program = r#"
+string left = "";
+string right = "";
+left = "apple";
+right = "apple";
+if (left != right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "banana";
+if (left != right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "banana";
+right = "apple";
+if (left != right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "app";
+right = "apple";
+if (left != right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "app";
+if (left != right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "";
+right = "a";
+if (left != right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "Zebra";
+right = "apple";
+if (left != right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FTTTTTT";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn string_greater() {
+    let program = r#"
+string left = "";
+string right = "";
+left = "apple";
+right = "apple";
+if (left > right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "banana";
+if (left > right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "banana";
+right = "apple";
+if (left > right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "app";
+right = "apple";
+if (left > right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "app";
+if (left > right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "";
+right = "a";
+if (left > right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "Zebra";
+right = "apple";
+if (left > right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FFTFTFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn string_less() {
+    let program = r#"
+string left = "";
+string right = "";
+left = "apple";
+right = "apple";
+if (left < right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "banana";
+if (left < right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "banana";
+right = "apple";
+if (left < right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "app";
+right = "apple";
+if (left < right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "app";
+if (left < right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "";
+right = "a";
+if (left < right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "Zebra";
+right = "apple";
+if (left < right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "FTFTFTT";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn string_greater_or_equal() {
+    let program = r#"
+string left = "";
+string right = "";
+left = "apple";
+right = "apple";
+if (left >= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "banana";
+if (left >= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "banana";
+right = "apple";
+if (left >= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "app";
+right = "apple";
+if (left >= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "app";
+if (left >= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "";
+right = "a";
+if (left >= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "Zebra";
+right = "apple";
+if (left >= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "TFTFTFF";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn string_less_or_equal() {
+    let program = r#"
+string left = "";
+string right = "";
+left = "apple";
+right = "apple";
+if (left <= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "banana";
+if (left <= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "banana";
+right = "apple";
+if (left <= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "app";
+right = "apple";
+if (left <= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "apple";
+right = "app";
+if (left <= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "";
+right = "a";
+if (left <= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+left = "Zebra";
+right = "apple";
+if (left <= right) {
+    "T" -> @;
+} else {
+    "F" -> @;
+};
+"#;
+
+    let expected_output = "TTFTFTT";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn string_comparison_with_concatenation() {
+    let program = r#"
+string answer = "ye";
+if (answer .. "s" == "yes") {
+    "Yes!" -> @;
+} else {
+    "No!" -> @;
+};
+"#;
+
+    let expected_output = "Yes!";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn string_comparison_with_null_characters() {
+    // Strings are compared by their lengths, not by where their first null character is
+    let program = r#"
+("a\x00b" == "a\x00c") -> @;
+("a\u{0}" == "a") -> @;
+("a" < "a\x00") -> @;
+("a\x00b" == "a\x00b") -> @;
+"#;
+
+    let expected_output = "falsefalsetruetrue";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}
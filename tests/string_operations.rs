@@ -0,0 +1,194 @@
+use stdext::function_name;
+
+mod common;
+
+#[test]
+fn concatenation_1() {
+    let program = r#"
+// Concatenating builds a new string, so the left operand is left alone
+string s = "abc";
+string t = s .. "def";
+s -> @;
+t -> @;
+s .. "!" -> @;
+s -> @;
+"#;
+
+    let expected_output = "abcabcdefabc!abc";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn concatenation_2() {
+    let program = r#"
+// Strings grow as needed, far beyond the old fixed-size buffers
+string s = "";
+string t = "";
+for (int i = 0; i < 1500; i = i + 1) {
+    s = s .. "ab";
+    if (i % 2 == 0) {
+        t = t .. "abab";
+    };
+};
+s == t -> @;
+s .. s == t .. t -> @;
+s -> @;
+"#;
+
+    let expected_output = "truetrue".to_string() + &"ab".repeat(1500);
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn assignment() {
+    let program = r#"
+// A variable keeps its own copy of the string, so changing one variable never changes another
+string a = "short";
+string b = a;
+a = "a much longer string than before";
+b -> @;
+a -> @;
+a = "tiny";
+a -> @;
+a = a;
+a -> @;
+"#;
+
+    let expected_output = "shorta much longer string than beforetinytiny";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn long_input() {
+    let program = r#"
+string line <- @;
+int number <- @;
+line .. "|" -> @;
+number + 1 -> @;
+"#;
+
+    let line = "x".repeat(5000);
+    let input = format!("{line}\r\n41\n");
+
+    let expected_output = format!("{line}|42");
+
+    assert_eq!(
+        common::compile_and_get_stripped_output_with_input(program, function_name!(), &input),
+        expected_output
+    );
+}
+
+#[test]
+fn freed_memory_is_reused() {
+    let program = r#"
+// Every iteration builds (and frees) temporary strings, and the arrays are handed the freed memory, which has
+// to start out zeroed just like fresh memory does
+int total = 0;
+string last;
+for (int i = 0; i < 20000; i = i + 1) {
+    string t = (string) i .. "abc" .. (string) (i * 2);
+    int[4] values;
+    total = total + values[0] + values[1] + values[2] + values[3];
+    last = t;
+};
+total -> @;
+last -> @;
+"#;
+
+    let expected_output = "019999abc39998";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn strings_returned_from_functions() {
+    let program = r#"
+string twice(string s) {
+    string result = s .. s;
+    return result;
+};
+string name = "ab";
+for (int i = 0; i < 3; i = i + 1) {
+    name = twice(name);
+};
+name -> @;
+twice("x") .. twice(name .. "!") == "xx" .. name .. "!" .. name .. "!" -> @;
+"#;
+
+    let expected_output = "abababababababab".to_string() + "true";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn concatenate_assignment() {
+    let program = r#"
+string s = "a";
+for (int i = 0; i < 3; i++) {
+    s ..= "b" .. (string)i;
+};
+s -> @;
+"#;
+
+    let expected_output = "ab0b1b2";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+}
+
+#[test]
+fn escape_sequences() {
+    let program = r#"
+"a\tb\\c\"d\x41\u{e9}\u{1F600}" -> @;
+"line one\nline two" -> @;
+", 0 `backquotes` and 'quotes' ; \\" -> @;
+"#;
+
+    let expected_output =
+        "a\tb\\c\"dA\u{e9}\u{1F600}\nline one\nline two\n, 0 `backquotes` and 'quotes' ; \\\n";
+
+    let output = common::compile_and_get_output(program, function_name!());
+
+    assert!(output.contains(expected_output));
+}
+
+#[test]
+fn byte_escape_sequences() {
+    let program = r#"
+// \xNN makes a single byte, so two of them can spell out a character that takes two bytes
+"\xC3\xA9" == "\u{e9}" -> @;
+"\xFF" > "\x7F" -> @;
+"\xff\x80" == "\xFF" .. "\x80" -> @;
+"#;
+
+    let expected_output = "truetruetrue";
+
+    assert_eq!(
+        common::compile_and_get_stripped_output(program, function_name!()),
+        expected_output
+    );
+    assert_eq!(
+        common::interpret_and_get_stripped_output(program, ""),
+        expected_output
+    );
+}